@@ -0,0 +1,162 @@
+use std::fs;
+use std::path;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use hex;
+use num_cpus;
+use toml;
+use params;
+use {Sha256Hash, sha256hash_from_slice};
+
+/// Read if it exists and no other config file is given
+pub const DEFAULT_CONFIG_FILE: &str = "./bongo_cat_coin.toml";
+
+/// Settings as they appear in the config file, anything left out falls back to the network's defaults
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    network: Option<String>,
+    bind_address: Option<IpAddr>,
+    p2p_port: Option<u16>,
+    graphql_port: Option<u16>,
+    data_dir: Option<String>,
+    mining_threads: Option<usize>,
+    miner_address: Option<String>,
+    peers: Option<Vec<SocketAddr>>,
+    sync_peer: Option<SocketAddr>,
+}
+
+#[derive(Debug)]
+pub struct Config {
+    pub params: &'static params::NetworkParams,
+    pub bind_address: IpAddr,
+    pub p2p_port: u16,
+    pub graphql_port: u16,
+    pub data_dir: String,
+    pub mining_threads: usize,
+    pub miner_address: Option<Sha256Hash>,
+    pub peers: Vec<SocketAddr>,
+    /// Peer to download the chain from before starting to mine
+    pub sync_peer: Option<SocketAddr>,
+}
+
+pub fn cli() -> App<'static, 'static> {
+    App::new("bongo_cat_coin")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Bongo cat coin node")
+        .setting(AppSettings::VersionlessSubcommands)
+        .arg(Arg::with_name("config").long("config").short("c").value_name("FILE").global(true)
+            .env("BONGO_CAT_COIN_CONFIG").help("TOML config file to read settings from"))
+        .arg(Arg::with_name("network").long("network").value_name("NAME").global(true)
+            .env("BONGO_CAT_COIN_NETWORK").possible_values(&["mainnet", "testnet", "regtest"]).help("Network to join"))
+        .arg(Arg::with_name("bind_address").long("bind").value_name("ADDRESS").global(true)
+            .env("BONGO_CAT_COIN_BIND_ADDRESS").help("Address to listen for peers and GraphQL requests on"))
+        .arg(Arg::with_name("p2p_port").long("p2p-port").value_name("PORT").global(true)
+            .env("BONGO_CAT_COIN_P2P_PORT").help("Port to listen for peers on"))
+        .arg(Arg::with_name("graphql_port").long("graphql-port").value_name("PORT").global(true)
+            .env("BONGO_CAT_COIN_GRAPHQL_PORT").help("Port to serve GraphQL on"))
+        .arg(Arg::with_name("data_dir").long("data-dir").value_name("DIR").global(true)
+            .env("BONGO_CAT_COIN_DATA_DIR").help("Directory blocks are stored in"))
+        .arg(Arg::with_name("mining_threads").long("mining-threads").value_name("COUNT").global(true)
+            .env("BONGO_CAT_COIN_MINING_THREADS").help("Number of threads to mine with, defaults to one per CPU"))
+        .arg(Arg::with_name("miner_address").long("miner-address").value_name("ADDRESS").global(true)
+            .env("BONGO_CAT_COIN_MINER_ADDRESS").help("Hex address block rewards are paid to"))
+        .arg(Arg::with_name("peers").long("peer").value_name("HOST:PORT").global(true)
+            .multiple(true).number_of_values(1).use_delimiter(true)
+            .env("BONGO_CAT_COIN_PEERS").help("Peer to keep a connection open to, may be given more than once"))
+        .arg(Arg::with_name("sync_peer").long("sync-peer").value_name("HOST:PORT").global(true)
+            .env("BONGO_CAT_COIN_SYNC_PEER").help("Peer to download the chain from before mining"))
+        .subcommand(SubCommand::with_name("run")
+            .about("Runs the node, this is the default"))
+        .subcommand(SubCommand::with_name("init")
+            .about("Creates the data directory and stores the genesis block"))
+        .subcommand(SubCommand::with_name("verify")
+            .about("Checks every stored block, exiting with an error if any are invalid"))
+        .subcommand(SubCommand::with_name("export")
+            .about("Writes the main chain to a file")
+            .arg(Arg::with_name("file").required(true).value_name("FILE")))
+        .subcommand(SubCommand::with_name("import")
+            .about("Adds the blocks in a file written by export to the stored chain")
+            .arg(Arg::with_name("file").required(true).value_name("FILE")))
+}
+
+fn read_file_config(matches: &ArgMatches) -> Result<FileConfig, String> {
+    let file = match matches.value_of("config") {
+        Some(file) => file,
+        None => {
+            if !path::Path::new(DEFAULT_CONFIG_FILE).exists() {
+                return Ok(FileConfig::default());
+            }
+            DEFAULT_CONFIG_FILE
+        }
+    };
+    let contents = match fs::read_to_string(file) {
+        Ok(contents) => contents,
+        Err(e) => return Err(format!("Cannot read config file {}: {}", file, e))
+    };
+    match toml::from_str(&contents) {
+        Ok(config) => Ok(config),
+        Err(e) => Err(format!("Invalid config file {}: {}", file, e))
+    }
+}
+
+/// A flag or environment variable if given, otherwise the config file's value
+fn setting<T: FromStr>(matches: &ArgMatches, name: &str, file_value: Option<T>) -> Result<Option<T>, String> {
+    match matches.value_of(name) {
+        Some(value) => match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(format!("Invalid {}: {}", name, value))
+        },
+        None => Ok(file_value)
+    }
+}
+
+impl Config {
+    /// Settings are taken from flags first, then environment variables, then the config file, then the defaults
+    /// for the chosen network
+    pub fn from_matches(matches: &ArgMatches) -> Result<Self, String> {
+        let file = read_file_config(matches)?;
+
+        let network: String = setting(matches, "network", file.network)?.unwrap_or("mainnet".to_string());
+        let params = match params::from_name(&network) {
+            Some(params) => params,
+            None => return Err(format!("Unknown network {}, expected mainnet, testnet or regtest", network))
+        };
+
+        let miner_address = match setting::<String>(matches, "miner_address", file.miner_address)? {
+            Some(address) => match hex::decode(&address) {
+                Ok(ref address) if address.len() == 32 => Some(sha256hash_from_slice(address)),
+                _ => return Err("Miner address must be a 32 byte hex address".to_string())
+            },
+            None => None
+        };
+
+        let peers = match matches.values_of("peers") {
+            Some(peers) => {
+                peers.filter(|peer| !peer.trim().is_empty())
+                    .map(|peer| peer.trim().parse().map_err(|_| format!("Invalid peer: {}", peer)))
+                    .collect::<Result<Vec<SocketAddr>, String>>()?
+            }
+            None => file.peers.unwrap_or(vec![])
+        };
+
+        let mining_threads = setting(matches, "mining_threads", file.mining_threads)?.unwrap_or(num_cpus::get());
+        if mining_threads == 0 {
+            return Err("Must mine with at least one thread".to_string());
+        }
+
+        Ok(Self {
+            params,
+            bind_address: setting(matches, "bind_address", file.bind_address)?
+                .unwrap_or(IpAddr::from([127, 0, 0, 1])),
+            p2p_port: setting(matches, "p2p_port", file.p2p_port)?.unwrap_or(params.p2p_port),
+            graphql_port: setting(matches, "graphql_port", file.graphql_port)?.unwrap_or(params.graphql_port),
+            data_dir: setting(matches, "data_dir", file.data_dir)?.unwrap_or(params.data_dir.to_string()),
+            mining_threads,
+            miner_address,
+            peers,
+            sync_peer: setting(matches, "sync_peer", file.sync_peer)?,
+        })
+    }
+}
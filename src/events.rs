@@ -0,0 +1,36 @@
+use std::sync::Mutex;
+use futures::sync::mpsc;
+use {Block, Transaction};
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// A block joined the main chain, either on top of the tip or as part of a reorganisation
+    Block(Block),
+    /// A transaction was accepted into the mempool
+    Transaction(Transaction),
+}
+
+/// Hands every event to each subscriber. Publishing never blocks, subscribers that have gone away are dropped the
+/// next time an event is published.
+#[derive(Debug)]
+pub struct EventBus {
+    subscribers: Mutex<Vec<mpsc::UnboundedSender<Event>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<Event> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    pub fn publish(&self, event: Event) {
+        self.subscribers.lock().unwrap().retain(|subscriber| subscriber.unbounded_send(event.clone()).is_ok());
+    }
+}
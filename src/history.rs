@@ -0,0 +1,157 @@
+use hex;
+use scalar::U64;
+use {Blockchain, Sha256Hash, Transaction};
+
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+pub const MAX_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, GraphQLEnum)]
+pub enum Direction {
+    #[graphql(name = "SENT")]
+    Sent,
+    #[graphql(name = "RECEIVED")]
+    Received,
+    #[graphql(name = "SELF")]
+    ToSelf,
+}
+
+/// A main chain transaction as seen from one of the addresses involved in it
+#[derive(Debug, Clone)]
+pub struct AddressTransaction {
+    transaction: Transaction,
+    direction: Direction,
+    /// The other address, or the address itself when it paid itself
+    counterparty: Sha256Hash,
+    block_id: u64,
+    confirmations: u64,
+}
+
+graphql_object!(AddressTransaction: () |&self|{
+    field transaction() -> Transaction {
+        self.transaction.clone()
+    }
+
+    field direction() -> Direction {
+        self.direction
+    }
+
+    field counterparty() -> String {
+        hex::encode_upper(&self.counterparty)
+    }
+
+    field block_id() -> U64 {
+        U64(self.block_id)
+    }
+
+    field confirmations() -> U64 {
+        U64(self.confirmations)
+    }
+});
+
+#[derive(Debug, Clone)]
+pub struct AddressTransactionEdge {
+    /// Pass as `after` to get the transactions that follow this one
+    cursor: Sha256Hash,
+    node: AddressTransaction,
+}
+
+graphql_object!(AddressTransactionEdge: () |&self|{
+    field cursor() -> String {
+        hex::encode_upper(&self.cursor)
+    }
+
+    field node() -> AddressTransaction {
+        self.node.clone()
+    }
+});
+
+#[derive(Debug, Clone)]
+pub struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<Sha256Hash>,
+}
+
+graphql_object!(PageInfo: () |&self|{
+    field has_next_page() -> bool {
+        self.has_next_page
+    }
+
+    field end_cursor() -> Option<String> {
+        self.end_cursor.map(|cursor| hex::encode_upper(&cursor))
+    }
+});
+
+#[derive(Debug, Clone)]
+pub struct AddressTransactionConnection {
+    edges: Vec<AddressTransactionEdge>,
+    page_info: PageInfo,
+}
+
+graphql_object!(AddressTransactionConnection: () |&self|{
+    field edges() -> Vec<AddressTransactionEdge> {
+        self.edges.clone()
+    }
+
+    field page_info() -> PageInfo {
+        self.page_info.clone()
+    }
+});
+
+/// Which way the transaction moves coins for the address, and the address on the other side
+pub fn direction(transaction: &Transaction, address: &Sha256Hash) -> (Direction, Sha256Hash) {
+    if transaction.sender == transaction.recipient {
+        (Direction::ToSelf, transaction.recipient)
+    } else if transaction.sender == *address {
+        (Direction::Sent, transaction.recipient)
+    } else {
+        (Direction::Received, transaction.sender)
+    }
+}
+
+/// Up to `first` transactions sending to or from the address, newest first, starting after the transaction whose
+/// id is `after`
+pub fn address_transactions(chain: &Blockchain, address: &Sha256Hash, after: Option<&Sha256Hash>, first: usize)
+                            -> Result<AddressTransactionConnection, String> {
+    let ids: &[Sha256Hash] = match chain.address_index.get(address) {
+        Some(ids) => ids,
+        None => &[]
+    };
+    let mut remaining = ids.iter().rev().peekable();
+    match after {
+        Some(after) => {
+            if !remaining.any(|id| id == after) {
+                return Err(format!("Unknown cursor {}", hex::encode_upper(after)));
+            }
+        }
+        None => {}
+    }
+
+    let mut edges = vec![];
+    while edges.len() < first {
+        let id = match remaining.next() {
+            Some(id) => id,
+            None => break
+        };
+        let (height, _) = chain.transaction_index[id];
+        let transaction = chain.transaction(id).expect("Address index refers to a transaction that isn't indexed");
+        let (direction, counterparty) = direction(&transaction, address);
+        edges.push(AddressTransactionEdge {
+            cursor: *id,
+            node: AddressTransaction {
+                transaction,
+                direction,
+                counterparty,
+                block_id: height,
+                confirmations: chain.main_chain.len() as u64 - height,
+            },
+        });
+    }
+
+    Ok(AddressTransactionConnection {
+        page_info: PageInfo {
+            has_next_page: remaining.peek().is_some(),
+            end_cursor: edges.last().map(|edge| edge.cursor),
+        },
+        edges,
+    })
+}
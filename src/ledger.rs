@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use hex;
+use scalar::U64;
+use {Block, BlockData, Sha256Hash};
+
+#[derive(Debug, Clone)]
+pub struct AccountBalance {
+    address: Sha256Hash,
+    balance: u64,
+}
+
+graphql_object!(AccountBalance: () |&self|{
+    field address() -> String {
+        hex::encode_upper(&self.address)
+    }
+
+    field balance() -> U64 {
+        U64(self.balance)
+    }
+});
+
+#[derive(Debug)]
+pub struct Ledger {
+    balances: HashMap<Sha256Hash, u64>,
+    /// Number of transactions each address has sent, which is the nonce its next transaction must use
+    nonces: HashMap<Sha256Hash, u64>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self {
+            balances: HashMap::new(),
+            nonces: HashMap::new(),
+        }
+    }
+
+    pub fn balance(&self, address: &Sha256Hash) -> u64 {
+        match self.balances.get(address) {
+            Some(balance) => *balance,
+            None => 0
+        }
+    }
+
+    pub fn nonce(&self, address: &Sha256Hash) -> u64 {
+        match self.nonces.get(address) {
+            Some(nonce) => *nonce,
+            None => 0
+        }
+    }
+
+    pub fn rich_list(&self, limit: usize) -> Vec<AccountBalance> {
+        let mut accounts: Vec<AccountBalance> = self.balances.iter()
+            .map(|(address, balance)| AccountBalance {
+                address: *address,
+                balance: *balance,
+            })
+            .collect();
+        accounts.sort_by(|a, b| b.balance.cmp(&a.balance).then(a.address.cmp(&b.address)));
+        accounts.truncate(limit);
+        accounts
+    }
+
+    fn credit(&mut self, address: &Sha256Hash, amount: u64) {
+        *self.balances.entry(*address).or_insert(0) += amount;
+    }
+
+    fn debit(&mut self, address: &Sha256Hash, amount: u64) -> Result<(), String> {
+        let balance = self.balance(address);
+        if balance < amount {
+            return Err(format!("{} has a balance of {} but tried to spend {}",
+                               hex::encode_upper(address), balance, amount));
+        }
+        if balance == amount {
+            self.balances.remove(address);
+        } else {
+            self.balances.insert(*address, balance - amount);
+        }
+        Ok(())
+    }
+
+    fn apply_data(&mut self, data: &Box<BlockData>) -> Result<(), String> {
+        match data.as_coinbase() {
+            Some(coinbase) => {
+                self.credit(&coinbase.recipient, coinbase.amount);
+                return Ok(());
+            }
+            None => {}
+        }
+        match data.as_transaction() {
+            Some(transaction) => {
+                let nonce = self.nonce(&transaction.sender);
+                if transaction.nonce != nonce {
+                    return Err(format!("{} sent a transaction with nonce {} but its next nonce is {}",
+                                       hex::encode_upper(&transaction.sender), transaction.nonce, nonce));
+                }
+                let total = match transaction.amount.checked_add(transaction.fee) {
+                    Some(total) => total,
+                    None => return Err("Transaction amount and fee overflow".to_string())
+                };
+                self.debit(&transaction.sender, total)?;
+                self.credit(&transaction.recipient, transaction.amount);
+                self.nonces.insert(transaction.sender, nonce + 1);
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn revert_data(&mut self, data: &Box<BlockData>) {
+        match data.as_coinbase() {
+            Some(coinbase) => {
+                self.debit(&coinbase.recipient, coinbase.amount)
+                    .expect("Reverted coinbase that was never applied");
+                return;
+            }
+            None => {}
+        }
+        match data.as_transaction() {
+            Some(transaction) => {
+                self.debit(&transaction.recipient, transaction.amount)
+                    .expect("Reverted transaction that was never applied");
+                self.credit(&transaction.sender, transaction.amount + transaction.fee);
+                if transaction.nonce == 0 {
+                    self.nonces.remove(&transaction.sender);
+                } else {
+                    self.nonces.insert(transaction.sender, transaction.nonce);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Applies every entry in the block, or none of them if any would overspend
+    pub fn apply_block(&mut self, block: &Block) -> Result<(), String> {
+        let inner = block.inner.read().unwrap();
+
+        for (i, data) in inner.data.iter().enumerate() {
+            match self.apply_data(data) {
+                Ok(_) => {}
+                Err(e) => {
+                    for data in inner.data[..i].iter().rev() {
+                        self.revert_data(data);
+                    }
+                    return Err(format!("Block #{}: {}", inner.id, e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn revert_block(&mut self, block: &Block) {
+        let inner = block.inner.read().unwrap();
+        for data in inner.data.iter().rev() {
+            self.revert_data(data);
+        }
+    }
+}
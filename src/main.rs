@@ -10,33 +10,69 @@ extern crate regex;
 extern crate num_bigint;
 extern crate num_traits;
 extern crate num_cpus;
-extern crate timer;
 extern crate ctrlc;
+extern crate clap;
+extern crate toml;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+extern crate futures;
+extern crate bytes;
+#[macro_use]
+extern crate serde_derive;
 
 mod proto;
+mod miner;
+mod ledger;
+mod validation;
+mod merkle;
+mod p2p;
+mod sync;
+mod params;
+mod config;
+mod store;
+mod mempool;
+mod history;
+mod scalar;
+mod events;
+mod subscription;
+mod rpc;
 
 use warp::Filter;
+use bytes::Buf;
 use crypto::sha2::Sha256;
 use crypto::digest::Digest;
+use crypto::ed25519;
 use chrono::prelude::*;
 use std::fmt;
 use std::thread;
+use std::time;
 use std::fs;
 use std::io;
 use std::path;
+use std::net::SocketAddr;
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock, mpsc};
+use std::sync::{Arc, RwLock};
 use juniper::FieldResult;
 use proto::chain;
 use regex::Regex;
 use protobuf::Message;
 use num_bigint::BigUint;
-use num_traits::One;
-use timer::Timer;
+use num_traits::{One, Zero};
+use scalar::U64;
 
 pub type Sha256Hash = [u8; 32];
-
-const GENESIS_DIFFICULTY: u8 = 0xa;
+pub type PublicKey = [u8; 32];
+
+const MIN_DIFFICULTY: u8 = 1;
+const TARGET_BLOCK_TIME: i64 = 30;
+const RETARGET_INTERVAL: u64 = 10;
+/// Most bytes of data a block can hold, coinbase included
+const MAX_BLOCK_SIZE: usize = 1024 * 1024;
+/// How many nonces each mining thread tries between checks of whether to give up
+const ABORT_CHECK_INTERVAL: u64 = 1024;
+/// Seconds between saves of the mempool while the node is running, it's also saved on shutdown
+const PENDING_SAVE_INTERVAL: u64 = 60;
 
 fn sha256hash_from_slice(bytes: &[u8]) -> Sha256Hash {
     let mut array = [0; 32];
@@ -45,19 +81,31 @@ fn sha256hash_from_slice(bytes: &[u8]) -> Sha256Hash {
     array
 }
 
+fn address_from_public_key(public_key: &PublicKey) -> Sha256Hash {
+    let mut hasher = Sha256::new();
+    let mut address = Sha256Hash::default();
+    hasher.input(public_key);
+    hasher.result(&mut address);
+    address
+}
+
 trait BlockData: Sync + Send {
     fn data(&self) -> Vec<u8>;
     fn box_clone(&self) -> Box<BlockData>;
     fn debug(&self, f: &mut fmt::Formatter) -> fmt::Result;
+    fn is_valid(&self) -> bool { true }
+    fn fee(&self) -> u64 { 0 }
 
     fn as_binary_data(&self) -> Option<&BinaryData> { None }
     fn as_transaction(&self) -> Option<&Transaction> { None }
+    fn as_coinbase(&self) -> Option<&Coinbase> { None }
 }
 
-graphql_union!(<'a> &'a BlockData: () as "BlockData" |&self| {
+graphql_union!(Box<BlockData>: () as "BlockData" |&self| {
     instance_resolvers: |_| {
         &BinaryData => self.as_binary_data(),
         &Transaction => self.as_transaction(),
+        &Coinbase => self.as_coinbase(),
     }
 });
 
@@ -78,10 +126,13 @@ struct BlockInner {
     id: u64,
     timestamp: i64,
     nonce: u64,
+    difficulty: u8,
     prev_block_hash: Sha256Hash,
+    merkle_root: Sha256Hash,
     data: Vec<Box<BlockData>>,
     prev_block_index: i64,
     next_block_indexes: Vec<i64>,
+    chain_work: BigUint,
 }
 
 #[derive(Debug, Clone)]
@@ -89,25 +140,58 @@ struct Block {
     inner: Arc<RwLock<BlockInner>>
 }
 
-graphql_object!(Block: () |&self|{
-    field id() -> i32 {
-        self.inner.read().unwrap().id as i32
+graphql_object!(Block: Context |&self|{
+    field id() -> U64 {
+        U64(self.inner.read().unwrap().id)
+    }
+
+    field hash() -> String {
+        hex::encode_upper(&self.hash())
+    }
+
+    field timestamp() -> U64 as "Seconds since the Unix epoch" {
+        U64(self.inner.read().unwrap().timestamp as u64)
+    }
+
+    field time() -> String as "The timestamp in RFC 3339 format" {
+        Utc.timestamp_opt(self.inner.read().unwrap().timestamp, 0).unwrap().to_rfc3339()
     }
 
-    field timestamp() -> i32 {
-        self.inner.read().unwrap().timestamp as i32
+    field nonce() -> U64 {
+        U64(self.inner.read().unwrap().nonce)
     }
 
-    field nonce() -> i32 {
-        self.inner.read().unwrap().nonce as i32
+    field difficulty() -> i32 {
+        self.inner.read().unwrap().difficulty as i32
     }
 
     field prev_block_hash() -> String {
         hex::encode_upper(&self.inner.read().unwrap().prev_block_hash)
     }
 
-    field data() -> &BlockData {
-        Box::leak(self.inner.read().unwrap().data[0].clone())
+    field data() -> Vec<Box<BlockData>> {
+        self.inner.read().unwrap().data.clone()
+    }
+
+    field parent(&executor) -> Option<Block> {
+        let chain = executor.context().blockchain.read().unwrap();
+        let prev_block_index = self.inner.read().unwrap().prev_block_index;
+        if prev_block_index < 0 {
+            return None;
+        }
+        Some(chain.blocks[prev_block_index as usize].clone())
+    }
+
+    field children(&executor) -> Vec<Block> {
+        let chain = executor.context().blockchain.read().unwrap();
+        self.inner.read().unwrap().next_block_indexes.iter()
+            .map(|index| chain.blocks[*index as usize].clone())
+            .collect()
+    }
+
+    field confirmations(&executor) -> U64 {
+        let chain = executor.context().blockchain.read().unwrap();
+        U64(chain.confirmations(self))
     }
 });
 
@@ -117,41 +201,59 @@ impl BlockInner {
         vec.extend(&convert_u64_to_u8_array(self.id));
         vec.extend(&convert_u64_to_u8_array(nonce));
         vec.extend(&convert_u64_to_u8_array(self.timestamp as u64));
+        vec.push(self.difficulty);
         vec.extend_from_slice(&self.prev_block_hash);
+        vec.extend_from_slice(&self.merkle_root);
         vec
     }
 
+    /// Only the fixed size header is hashed, the data is committed to by the merkle root
     fn hash_with_nonce(&self, nonce: u64) -> Sha256Hash {
         let mut hasher = Sha256::new();
         let mut hash = Sha256Hash::default();
 
         hasher.input(&self.headers(nonce));
 
-        for elm in self.data.iter() {
-            hasher.input(&elm.data());
-        }
-
         hasher.result(&mut hash);
         hash
     }
 
+    fn data_merkle_root(&self) -> Sha256Hash {
+        merkle::merkle_root(&self.data_hashes())
+    }
+
+    fn data_hashes(&self) -> Vec<Sha256Hash> {
+        self.data.iter()
+            .map(|data| merkle::leaf_hash(&data.data()))
+            .collect()
+    }
+
     fn hash(&self) -> Sha256Hash {
         self.hash_with_nonce(self.nonce)
     }
 }
 
 impl Block {
-    fn mine(&self, difficulty: u8) -> Option<u64> {
+    fn mine(&self) -> Option<u64> {
+        self.mine_until(|| false, num_cpus::get())
+    }
+
+    /// Searches for a nonce on `thread_count` threads, giving up once `abort` returns true. `abort` is only called
+    /// every `ABORT_CHECK_INTERVAL` nonces so it can be slower than hashing the block.
+    fn mine_until<F>(&self, abort: F, thread_count: usize) -> Option<u64>
+        where F: Fn() -> bool + Send + Sync + 'static {
+        let abort = Arc::new(abort);
         let found_flag = Arc::new(RwLock::new(false));
         let nonce_out = Arc::new(RwLock::new(0 as u64));
-        let target = Arc::new(BigUint::one() << (256 - difficulty as usize));
         let inner = self.inner.read().unwrap();
+        let target = Arc::new(BigUint::one() << (256 - inner.difficulty as usize));
         let mut threads = vec![];
 
-        println!("Started mining block #{} with difficulty {}", inner.id, difficulty);
+        println!("Started mining block #{} with difficulty {}", inner.id, inner.difficulty);
 
-        for _ in 0..num_cpus::get() {
+        for _ in 0..thread_count {
             let found_flag = Arc::clone(&found_flag);
+            let abort = Arc::clone(&abort);
             let nonce_out = Arc::clone(&nonce_out);
             let block = Arc::clone(&self.inner);
             let target = Arc::clone(&target);
@@ -159,7 +261,7 @@ impl Block {
                 for nonce in 0..std::u64::MAX {
                     {
                         let mut flag = found_flag.read().unwrap();
-                        if *flag {
+                        if *flag || (nonce % ABORT_CHECK_INTERVAL == 0 && abort()) {
                             return;
                         }
                     }
@@ -197,72 +299,85 @@ impl Block {
         }
     }
 
-    fn is_valid(&self, difficulty: u8) -> bool {
-        let target = BigUint::one() << (256 - difficulty as usize);
+    fn hash(&self) -> Sha256Hash {
         let inner = self.inner.read().unwrap();
-        let hash = inner.hash();
-        let hash = BigUint::from_bytes_be(&hash);
-        hash < target
+        inner.hash()
     }
 
-    fn hash(&self) -> Sha256Hash {
+    /// Inclusion proof for the data entry at `index`
+    fn data_proof(&self, index: usize) -> Option<merkle::MerkleProof> {
         let inner = self.inner.read().unwrap();
-        inner.hash()
+        let leaves = inner.data_hashes();
+        let proof = merkle::merkle_proof(&leaves, index).map(|siblings| merkle::MerkleProof {
+            block_hash: inner.hash(),
+            merkle_root: inner.merkle_root,
+            index,
+            leaf_count: leaves.len(),
+            leaf: leaves[index],
+            siblings,
+        })?;
+        debug_assert!(merkle::verify_data_proof(&inner, &inner.data[index].data(), &proof));
+        Some(proof)
     }
 
-    pub fn new(data: &Vec<Box<BlockData>>, prev_block_hash: Sha256Hash, prev_block_index: i64, id: u64) -> Self {
-        let inner = BlockInner {
+    /// Expected number of hashes needed to mine this block
+    fn work(&self) -> BigUint {
+        BigUint::one() << self.inner.read().unwrap().difficulty as usize
+    }
+
+    pub fn new(data: &Vec<Box<BlockData>>, prev_block_hash: Sha256Hash, prev_block_index: i64, id: u64,
+               difficulty: u8) -> Self {
+        let mut inner = BlockInner {
             id,
             prev_block_hash,
+            merkle_root: Sha256Hash::default(),
             timestamp: Utc::now().timestamp(),
             nonce: 0,
+            difficulty,
             data: data.to_owned().to_vec(),
             prev_block_index,
-            next_block_indexes: vec![]
+            next_block_indexes: vec![],
+            chain_work: BigUint::zero(),
         };
+        inner.merkle_root = inner.data_merkle_root();
         Self {
             inner: Arc::new(RwLock::new(inner))
         }
     }
 
-    pub fn restore(data: &Vec<Box<BlockData>>, prev_block_hash: Sha256Hash, id: u64, timestamp: i64,
-                   nonce: u64) -> Self {
+    pub fn restore(data: &Vec<Box<BlockData>>, prev_block_hash: Sha256Hash, merkle_root: Sha256Hash, id: u64,
+                   timestamp: i64, nonce: u64, difficulty: u8) -> Self {
         let inner = BlockInner {
             id,
             prev_block_hash,
+            merkle_root,
             timestamp,
             nonce,
+            difficulty,
             data: data.to_owned().to_vec(),
             prev_block_index: -1,
-            next_block_indexes: vec![]
+            next_block_indexes: vec![],
+            chain_work: BigUint::zero(),
         };
         Self {
             inner: Arc::new(RwLock::new(inner))
         }
     }
 
-    pub fn genesis() -> Self {
-        let block = Self::new(&vec![BinaryData::new(&b"Genesis block".to_vec())],
-                                  Sha256Hash::default(), 0,0);
-        match block.mine(GENESIS_DIFFICULTY) {
-            Some(nonce) => {
-                let mut inner = block.inner.read().unwrap().clone();
-                inner.nonce = nonce;
-                Self {
-                    inner: Arc::new(RwLock::new(inner))
-                }
-            }
-            None => {
-                panic!("Failed to mine genesis block");
-            }
+    pub fn genesis(params: &params::NetworkParams) -> Self {
+        let block = Self::new(&vec![BinaryData::new(&params.genesis_data.to_vec())],
+                              Sha256Hash::default(), -1, 0, params.genesis_difficulty);
+        {
+            let mut inner = block.inner.write().unwrap();
+            inner.timestamp = params.genesis_timestamp;
+            inner.nonce = params.genesis_nonce;
         }
+        block
     }
 
-    fn next_block(&self, index: i64) -> Self {
+    fn next_block(&self, index: i64, data: &Vec<Box<BlockData>>, difficulty: u8) -> Self {
         let inner = self.inner.read().unwrap();
-        let next_block = inner.id + 1;
-        Self::new(&vec![BinaryData::new(&format!("Block {}", next_block).as_bytes().to_vec())],
-                  inner.hash(), index, next_block)
+        Self::new(data, inner.hash(), index, inner.id + 1, difficulty)
     }
 }
 
@@ -284,15 +399,50 @@ struct Transaction {
     sender: Sha256Hash,
     recipient: Sha256Hash,
     amount: u64,
+    /// Paid to the miner on top of the amount
+    fee: u64,
+    /// Number of transactions the sender has sent before this one
+    nonce: u64,
+    public_key: PublicKey,
+    signature: Vec<u8>,
 }
 
-impl BlockData for Transaction {
-    fn data(&self) -> Vec<u8> {
-        let mut data = vec![1 as u8];
+impl Transaction {
+    /// The bytes covered by the signature:
+    /// `0x03 || sender || recipient || amount (u64 LE) || fee (u64 LE) || nonce (u64 LE) || public_key`
+    fn signing_data(&self) -> Vec<u8> {
+        let mut data = vec![3 as u8];
 
         data.extend_from_slice(&self.sender);
         data.extend_from_slice(&self.recipient);
         data.extend_from_slice(&convert_u64_to_u8_array(self.amount));
+        data.extend_from_slice(&convert_u64_to_u8_array(self.fee));
+        data.extend_from_slice(&convert_u64_to_u8_array(self.nonce));
+        data.extend_from_slice(&self.public_key);
+
+        data
+    }
+
+    /// The transaction's id, SHA-256 of the signed transaction as it's stored in a block
+    fn hash(&self) -> Sha256Hash {
+        let mut hasher = Sha256::new();
+        let mut hash = Sha256Hash::default();
+        hasher.input(&self.data());
+        hasher.result(&mut hash);
+        hash
+    }
+
+    /// Bytes the transaction takes up in a block
+    fn size(&self) -> usize {
+        self.data().len()
+    }
+}
+
+impl BlockData for Transaction {
+    fn data(&self) -> Vec<u8> {
+        let mut data = self.signing_data();
+
+        data.extend_from_slice(&self.signature);
 
         data
     }
@@ -303,20 +453,140 @@ impl BlockData for Transaction {
         write!(f, "{:?}", self)
     }
 
+    fn fee(&self) -> u64 {
+        self.fee
+    }
+
+    fn is_valid(&self) -> bool {
+        self.signature.len() == 64 &&
+            address_from_public_key(&self.public_key) == self.sender &&
+            ed25519::verify(&self.signing_data(), &self.public_key, &self.signature)
+    }
+
     fn as_transaction(&self) -> Option<&Transaction> { Some(&self) }
 }
 
 graphql_object!(Transaction: () |&self|{
+    field id() -> String {
+        hex::encode_upper(&self.hash())
+    }
+
     field sender() -> String {
         hex::encode_upper(&self.sender)
     }
 
     field recipient() -> String {
-        hex::encode_upper(&self.sender)
+        hex::encode_upper(&self.recipient)
+    }
+
+    field amount() -> U64 {
+        U64(self.amount)
+    }
+
+    field fee() -> U64 {
+        U64(self.fee)
+    }
+
+    field nonce() -> U64 {
+        U64(self.nonce)
+    }
+
+    field public_key() -> String {
+        hex::encode_upper(&self.public_key)
+    }
+
+    field signature() -> String {
+        hex::encode_upper(&self.signature)
+    }
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, GraphQLEnum)]
+enum TransactionState {
+    #[graphql(name = "PENDING")]
+    Pending,
+    #[graphql(name = "CONFIRMED")]
+    Confirmed,
+}
+
+#[derive(Debug, Clone)]
+struct TransactionStatus {
+    id: Sha256Hash,
+    state: TransactionState,
+    /// Height of the main chain block holding the transaction, and its position in the block's data
+    location: Option<(u64, usize)>,
+    confirmations: u64,
+}
+
+graphql_object!(TransactionStatus: () |&self|{
+    field id() -> String {
+        hex::encode_upper(&self.id)
+    }
+
+    field state() -> TransactionState {
+        self.state
+    }
+
+    field block_id() -> Option<U64> {
+        self.location.map(|(height, _)| U64(height))
+    }
+
+    field position() -> Option<i32> {
+        self.location.map(|(_, position)| position as i32)
+    }
+
+    field confirmations() -> U64 {
+        U64(self.confirmations)
+    }
+});
+
+#[derive(Debug, Clone)]
+struct Coinbase {
+    recipient: Sha256Hash,
+    amount: u64,
+    height: u64,
+}
+
+impl Coinbase {
+    pub fn new(recipient: Sha256Hash, height: u64, amount: u64) -> Box<BlockData> {
+        Box::new(Self {
+            recipient,
+            amount,
+            height,
+        })
     }
+}
+
+impl BlockData for Coinbase {
+    fn data(&self) -> Vec<u8> {
+        let mut data = vec![2 as u8];
+
+        data.extend_from_slice(&self.recipient);
+        data.extend_from_slice(&convert_u64_to_u8_array(self.amount));
+        data.extend_from_slice(&convert_u64_to_u8_array(self.height));
 
-    field amount() -> i32 {
-        self.amount as i32
+        data
+    }
+    fn box_clone(&self) -> Box<BlockData> {
+        Box::new((*self).clone())
+    }
+    fn debug(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+
+    fn as_coinbase(&self) -> Option<&Coinbase> { Some(&self) }
+}
+
+graphql_object!(Coinbase: () |&self|{
+    field recipient() -> String {
+        hex::encode_upper(&self.recipient)
+    }
+
+    field amount() -> U64 {
+        U64(self.amount)
+    }
+
+    field height() -> U64 {
+        U64(self.height)
     }
 });
 
@@ -359,58 +629,472 @@ graphql_object!(BinaryData: () |&self|{
 
 #[derive(Debug)]
 struct Blockchain {
+    params: &'static params::NetworkParams,
     blocks: Vec<Block>,
+    main_chain: Vec<usize>,
     hash_index_map: HashMap<Sha256Hash, i64>,
-    pending_data: Vec<Box<BlockData>>,
+    mempool: mempool::Mempool,
+    ledger: ledger::Ledger,
+    /// Height of the block and position in its data of every transaction on the main chain
+    transaction_index: HashMap<Sha256Hash, (u64, usize)>,
+    /// Ids of the main chain transactions sent or received by each address, oldest first
+    address_index: HashMap<Sha256Hash, Vec<Sha256Hash>>,
+    /// Where accepted blocks are written, left unset while loading the blocks already stored
+    store: Option<store::BlockStore>,
+    /// Announces new main chain blocks and pending transactions to subscribers
+    events: events::EventBus,
+    /// Goes up every time the mempool changes, so it's only saved again when there's something new
+    mempool_version: u64,
 }
 
 impl Blockchain {
-    pub fn new() -> Self {
+    pub fn new(params: &'static params::NetworkParams) -> Self {
         Self {
+            params,
             blocks: vec![],
-            pending_data: vec![],
+            main_chain: vec![],
+            mempool: mempool::Mempool::new(),
             hash_index_map: HashMap::new(),
+            ledger: ledger::Ledger::new(),
+            transaction_index: HashMap::new(),
+            address_index: HashMap::new(),
+            store: None,
+            events: events::EventBus::new(),
+            mempool_version: 0,
         }
     }
 
     fn init_genesis(&mut self) {
-        println!("Initialising genesis block");
-        self.blocks.push(Block::genesis());
-        println!("New chain initialised");
+        let genesis = Block::genesis(self.params);
+        println!("Initialising genesis block {}", hex::encode(genesis.hash()));
+        self.add_genesis(genesis).expect("Embedded genesis block is invalid");
+    }
+
+    /// Starts writing accepted blocks to `store`, first saving any blocks it doesn't have yet
+    fn attach_store(&mut self, mut store: store::BlockStore) -> io::Result<()> {
+        for block in self.blocks.iter() {
+            store.append(block)?;
+        }
+        self.store = Some(store);
+        self.save_pending();
+        Ok(())
     }
 
-    fn add_block(&mut self) {
-        let block: Block;
-        match self.blocks.last() {
-            Some(last_block) => {
-                block = last_block.next_block((self.blocks.len()-1) as i64);
+    fn main_chain_block(&self, height: usize) -> Option<&Block> {
+        self.main_chain.get(height).map(|index| &self.blocks[*index])
+    }
+
+    fn block_by_hash(&self, hash: &Sha256Hash) -> Option<&Block> {
+        self.hash_index_map.get(hash).map(|index| &self.blocks[*index as usize])
+    }
+
+    fn tip(&self) -> Option<&Block> {
+        self.main_chain.last().map(|index| &self.blocks[*index])
+    }
+
+    /// Number of main chain blocks from this one to the tip inclusive, 0 if it is on a side branch
+    fn confirmations(&self, block: &Block) -> u64 {
+        let id = block.inner.read().unwrap().id;
+        match self.main_chain_block(id as usize) {
+            Some(main_block) if Arc::ptr_eq(&main_block.inner, &block.inner) => self.main_chain.len() as u64 - id,
+            _ => 0
+        }
+    }
+
+    /// Hashes of main chain blocks from the tip back to genesis, getting sparser the further back they go, so a
+    /// peer can find where our chains fork without us sending every hash
+    fn locator(&self) -> Vec<Sha256Hash> {
+        let mut locator = vec![];
+        let mut height = self.main_chain.len() as i64 - 1;
+        let mut step = 1;
+        while height > 0 {
+            locator.push(self.blocks[self.main_chain[height as usize]].hash());
+            if locator.len() >= 10 {
+                step *= 2;
+            }
+            height -= step;
+        }
+        match self.main_chain.first() {
+            Some(index) => locator.push(self.blocks[*index].hash()),
+            None => {}
+        }
+        locator
+    }
+
+    /// Main chain blocks after the first locator hash that is on our main chain, or from genesis if none are
+    fn blocks_after_locator(&self, locator: &[Sha256Hash], max: usize) -> Vec<&Block> {
+        let start = locator.iter()
+            .filter_map(|hash| self.hash_index_map.get(hash))
+            .filter_map(|index| {
+                let height = self.blocks[*index as usize].inner.read().unwrap().id as usize;
+                match self.main_chain.get(height) {
+                    Some(main_index) if *main_index as i64 == *index => Some(height + 1),
+                    _ => None
+                }
+            })
+            .next()
+            .unwrap_or(0);
+        self.main_chain.iter()
+            .skip(start)
+            .take(max)
+            .map(|index| &self.blocks[*index])
+            .collect()
+    }
+
+    fn candidate_block(&self, miner_address: &Sha256Hash) -> Option<Block> {
+        match self.main_chain.last() {
+            Some(&tip_index) => {
+                let last_block = &self.blocks[tip_index];
+                let height = last_block.inner.read().unwrap().id + 1;
+                let coinbase_size = Coinbase::new(*miner_address, height, 0).data().len();
+                let mut data: Vec<Box<BlockData>> = self.mempool.select(MAX_BLOCK_SIZE - coinbase_size).into_iter()
+                    .map(|transaction| Box::new(transaction) as Box<BlockData>)
+                    .collect();
+                let fees = data.iter().fold(0 as u64, |total, data| total.saturating_add(data.fee()));
+                let amount = self.params.block_reward(height).saturating_add(fees);
+                data.insert(0, Coinbase::new(*miner_address, height, amount));
+                let difficulty = self.next_difficulty(tip_index);
+                let block = last_block.next_block(tip_index as i64, &data, difficulty);
+                {
+                    let mut inner = block.inner.write().unwrap();
+                    let median_time_past = validation::median_time_past(self, tip_index);
+                    if inner.timestamp <= median_time_past {
+                        inner.timestamp = median_time_past + 1;
+                    }
+                }
+                Some(block)
             }
             None => {
                 println!("No parent");
-                return;
+                None
             }
         }
-        match block.mine(GENESIS_DIFFICULTY) {
-            Some(nonce) => {
-                let mut inner = block.inner.read().unwrap().clone();
-                inner.nonce = nonce;
-                self.blocks.push(Block {
-                    inner: Arc::new(RwLock::new(inner))
-                })
+    }
+
+    /// Difficulty required of a block built on top of the block at `parent_index`.
+    ///
+    /// Every `RETARGET_INTERVAL` blocks the time taken to mine the previous interval is compared with
+    /// `TARGET_BLOCK_TIME`, and the difficulty moves by the nearest power of two (at most 2 bits either way).
+    fn next_difficulty(&self, parent_index: usize) -> u8 {
+        let parent = self.blocks[parent_index].inner.read().unwrap();
+        let height = parent.id + 1;
+        if !self.params.retarget || height % RETARGET_INTERVAL != 0 {
+            return parent.difficulty;
+        }
+
+        let mut first_index = parent_index;
+        for _ in 1..RETARGET_INTERVAL {
+            let prev_block_index = self.blocks[first_index].inner.read().unwrap().prev_block_index;
+            if prev_block_index < 0 {
+                return parent.difficulty;
+            }
+            first_index = prev_block_index as usize;
+        }
+        let first_timestamp = self.blocks[first_index].inner.read().unwrap().timestamp;
+
+        let expected = TARGET_BLOCK_TIME * (RETARGET_INTERVAL - 1) as i64;
+        let actual = std::cmp::max(parent.timestamp - first_timestamp, 1);
+        let adjustment = (expected as f64 / actual as f64).log2().round().max(-2.0).min(2.0) as i64;
+        let difficulty = (parent.difficulty as i64 + adjustment).max(MIN_DIFFICULTY as i64).min(255);
+        if difficulty != parent.difficulty as i64 {
+            println!("Retargeting difficulty at block #{} from {} to {}", height, parent.difficulty, difficulty);
+        }
+        difficulty as u8
+    }
+
+    fn add_genesis(&mut self, block: Block) -> Result<(), validation::BlockError> {
+        if self.blocks.len() != 0 {
+            return Err(validation::BlockError::GenesisExists);
+        }
+        let id = block.inner.read().unwrap().id;
+        if id != 0 {
+            return Err(validation::BlockError::NotGenesis(id));
+        }
+        validation::check_block_contents(self.params, &block, self.params.genesis_difficulty)?;
+        self.ledger.apply_block(&block).map_err(validation::BlockError::Overspend)?;
+
+        let chain_work = block.work();
+        block.inner.write().unwrap().chain_work = chain_work;
+        self.hash_index_map.insert(block.hash(), 0);
+        self.blocks.push(block);
+        self.main_chain.push(0);
+        self.index_transactions(0);
+        Ok(())
+    }
+
+    /// Adds a block anywhere in the block tree, switching the main chain over to its branch if
+    /// that branch now has the most cumulative work. The branch is replayed on the ledger before the block joins
+    /// the tree or the store, so a block that would overspend is never kept.
+    fn add_block(&mut self, block: Block) -> Result<(), validation::BlockError> {
+        let parent_index = validation::check_block(self, &block)?;
+
+        let tip_index = match self.main_chain.last() {
+            Some(index) => *index,
+            None => {
+                return Err(validation::BlockError::NoGenesis);
+            }
+        };
+        let extends_tip = parent_index == tip_index;
+        if extends_tip {
+            self.ledger.apply_block(&block).map_err(validation::BlockError::Overspend)?;
+        }
+        let id = block.inner.read().unwrap().id;
+        let chain_work = &self.blocks[parent_index].inner.read().unwrap().chain_work + block.work();
+        let branch = if !extends_tip && chain_work > self.blocks[tip_index].inner.read().unwrap().chain_work {
+            let (fork_height, connected) = self.branch(parent_index);
+            let disconnected = self.main_chain[fork_height + 1..].to_vec();
+            self.switch_ledger(&disconnected, &connected, &block).map_err(validation::BlockError::Overspend)?;
+            Some((fork_height, disconnected, connected))
+        } else {
+            None
+        };
+
+        // Only now that the block is known to be accepted is it written out
+        let stored = match self.store {
+            Some(ref mut store) => store.append(&block),
+            None => Ok(())
+        };
+        if let Err(e) = stored {
+            if extends_tip {
+                self.ledger.revert_block(&block);
+            }
+            match branch {
+                Some((_, ref disconnected, ref connected)) => {
+                    self.ledger.revert_block(&block);
+                    self.revert_switch(disconnected, connected);
+                }
+                None => {}
+            }
+            return Err(validation::BlockError::Storage(e.to_string()));
+        }
+
+        let index = self.blocks.len();
+        {
+            let mut inner = block.inner.write().unwrap();
+            inner.prev_block_index = parent_index as i64;
+            inner.chain_work = chain_work;
+        }
+        self.blocks[parent_index].inner.write().unwrap().next_block_indexes.push(index as i64);
+        self.hash_index_map.insert(block.hash(), index as i64);
+        self.blocks.push(block);
+
+        if extends_tip {
+            self.main_chain.push(index);
+            self.index_transactions(index);
+            self.mempool.update(&self.ledger, vec![]);
+            self.events.publish(events::Event::Block(self.blocks[index].clone()));
+        } else {
+            match branch {
+                Some((fork_height, disconnected, mut connected)) => {
+                    connected.push(index);
+                    self.reorganise(fork_height, disconnected, connected);
+                }
+                None => println!("Stored block #{} on a side branch", id)
+            }
+        }
+        self.mempool_version += 1;
+        Ok(())
+    }
+
+    /// The height of the main chain block the branch ending at `index` forks from, and the indexes of the branch's
+    /// blocks after it, oldest first
+    fn branch(&self, mut index: usize) -> (usize, Vec<usize>) {
+        let mut branch = vec![];
+        loop {
+            let inner = self.blocks[index].inner.read().unwrap();
+            if self.main_chain.get(inner.id as usize) == Some(&index) {
+                branch.reverse();
+                return (inner.id as usize, branch);
+            }
+            branch.push(index);
+            index = inner.prev_block_index as usize;
+        }
+    }
+
+    /// Rolls the ledger back over the `disconnected` main chain blocks and replays the `connected` side branch
+    /// blocks followed by `block`. If any of them overspends the ledger is left as it was.
+    fn switch_ledger(&mut self, disconnected: &[usize], connected: &[usize], block: &Block) -> Result<(), String> {
+        for &index in disconnected.iter().rev() {
+            self.ledger.revert_block(&self.blocks[index]);
+        }
+        for (i, &index) in connected.iter().enumerate() {
+            match self.ledger.apply_block(&self.blocks[index]) {
+                Ok(_) => {}
+                Err(e) => {
+                    self.revert_switch(disconnected, &connected[..i]);
+                    return Err(e);
+                }
+            }
+        }
+        match self.ledger.apply_block(block) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                self.revert_switch(disconnected, connected);
+                Err(e)
+            }
+        }
+    }
+
+    /// Undoes `switch_ledger` up to and including the `connected` blocks
+    fn revert_switch(&mut self, disconnected: &[usize], connected: &[usize]) {
+        for &index in connected.iter().rev() {
+            self.ledger.revert_block(&self.blocks[index]);
+        }
+        for &index in disconnected.iter() {
+            self.ledger.apply_block(&self.blocks[index]).expect("Failed to restore main chain");
+        }
+    }
+
+    /// Moves the main chain from the `disconnected` blocks after the fork to the `connected` branch, once the ledger
+    /// has been switched over. Transactions only in the old branch go back to the mempool.
+    fn reorganise(&mut self, fork_height: usize, disconnected: Vec<usize>, connected: Vec<usize>) {
+        for &index in disconnected.iter().rev() {
+            self.unindex_transactions(index);
+        }
+        for &index in connected.iter() {
+            self.index_transactions(index);
+        }
+        self.main_chain.truncate(fork_height + 1);
+        self.main_chain.extend(connected.iter());
+
+        let mut returned = vec![];
+        for &index in disconnected.iter() {
+            for data in self.blocks[index].inner.read().unwrap().data.iter() {
+                match data.as_transaction() {
+                    Some(transaction) => returned.push(transaction.clone()),
+                    None => {}
+                }
+            }
+        }
+        self.mempool.update(&self.ledger, returned);
+        for &index in connected.iter() {
+            self.events.publish(events::Event::Block(self.blocks[index].clone()));
+        }
+
+        println!("Reorganised chain at block #{}: {} blocks disconnected, {} blocks connected",
+                 fork_height, disconnected.len(), connected.len());
+    }
+
+    /// Adds the transactions in the block at `index` to the transaction and address indexes, called as it joins
+    /// the main chain
+    fn index_transactions(&mut self, index: usize) {
+        let inner = self.blocks[index].inner.read().unwrap();
+        for (position, data) in inner.data.iter().enumerate() {
+            match data.as_transaction() {
+                Some(transaction) => {
+                    let hash = transaction.hash();
+                    self.transaction_index.insert(hash, (inner.id, position));
+                    self.address_index.entry(transaction.sender).or_insert_with(Vec::new).push(hash);
+                    if transaction.recipient != transaction.sender {
+                        self.address_index.entry(transaction.recipient).or_insert_with(Vec::new).push(hash);
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Undoes `index_transactions`, blocks must be unindexed from the tip down
+    fn unindex_transactions(&mut self, index: usize) {
+        let inner = self.blocks[index].inner.read().unwrap();
+        for data in inner.data.iter().rev() {
+            match data.as_transaction() {
+                Some(transaction) => {
+                    let hash = transaction.hash();
+                    self.transaction_index.remove(&hash);
+                    for address in [transaction.sender, transaction.recipient].iter() {
+                        let now_empty = match self.address_index.get_mut(address) {
+                            Some(ids) => {
+                                if ids.last() == Some(&hash) {
+                                    ids.pop();
+                                } else {
+                                    ids.retain(|id| *id != hash);
+                                }
+                                ids.is_empty()
+                            }
+                            None => false
+                        };
+                        if now_empty {
+                            self.address_index.remove(address);
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// A transaction on the main chain or in the mempool
+    fn transaction(&self, hash: &Sha256Hash) -> Option<Transaction> {
+        match self.transaction_index.get(hash) {
+            Some(&(height, position)) => {
+                let block = &self.blocks[self.main_chain[height as usize]];
+                let inner = block.inner.read().unwrap();
+                inner.data[position].as_transaction().cloned()
             }
+            None => self.mempool.get(hash).cloned()
+        }
+    }
+
+    fn transaction_status(&self, hash: &Sha256Hash) -> Option<TransactionStatus> {
+        match self.transaction_index.get(hash) {
+            Some(&(height, position)) => Some(TransactionStatus {
+                id: *hash,
+                state: TransactionState::Confirmed,
+                location: Some((height, position)),
+                confirmations: self.main_chain.len() as u64 - height,
+            }),
             None => {
-                panic!("Failed to mine block");
+                if self.mempool.get(hash).is_none() {
+                    return None;
+                }
+                Some(TransactionStatus {
+                    id: *hash,
+                    state: TransactionState::Pending,
+                    location: None,
+                    confirmations: 0,
+                })
             }
         }
     }
 
-    fn add_data(&mut self, data: Box<BlockData>) {
-        self.pending_data.push(data);
+    fn add_transaction(&mut self, transaction: Transaction) -> Result<Sha256Hash, mempool::MempoolError> {
+        let hash = self.mempool.add(transaction.clone(), &self.ledger)?;
+        self.mempool_version += 1;
+        self.events.publish(events::Event::Transaction(transaction));
+        Ok(hash)
+    }
+
+    /// Writes the mempool next to the blocks so it survives a restart
+    fn save_pending(&self) {
+        match self.store {
+            Some(ref store) => {
+                if let Err(e) = store.save_mempool(&self.mempool.transactions()) {
+                    println!("Failed to save pending data: {}", e);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Puts back transactions saved by a previous run, dropping any that have been mined since or are no longer
+    /// valid. Returns how many were dropped.
+    fn restore_pending(&mut self, pending: Vec<Transaction>) -> usize {
+        let mut dropped = 0;
+        for transaction in pending {
+            if self.mempool.add(transaction, &self.ledger).is_err() {
+                dropped += 1;
+            }
+        }
+        dropped
     }
 }
 
+#[derive(Clone)]
 struct Context {
     blockchain: Arc<RwLock<Blockchain>>,
+    network: p2p::Network,
+    miner_address: Sha256Hash,
 }
 
 impl juniper::Context for Context {}
@@ -418,10 +1102,10 @@ impl juniper::Context for Context {}
 struct Query;
 
 graphql_object!(Query: Context |&self| {
-    field block(&executor, id: i32) -> FieldResult<Block> {
+    field block(&executor, id: U64) -> FieldResult<Block> {
         let context = executor.context();
         let chain = context.blockchain.read().unwrap();
-        match chain.blocks.get(id as usize) {
+        match chain.main_chain_block(id.0 as usize) {
             Some(block) => {
                 Ok(block.clone())
             }
@@ -431,32 +1115,203 @@ graphql_object!(Query: Context |&self| {
         }
     }
 
-    field blocks(&executor, start: i32, len: i32) -> FieldResult<Vec<Block>> {
+    field blockByHash(&executor, hash: String) -> FieldResult<Block> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+        let hash = match hex::decode(hash) {
+            Ok(ref hash) if hash.len() == 32 => sha256hash_from_slice(hash),
+            _ => {
+                return Err(juniper::FieldError::new("Invalid block hash", graphql_value!(None)));
+            }
+        };
+        match chain.block_by_hash(&hash) {
+            Some(block) => Ok(block.clone()),
+            None => Err(juniper::FieldError::new("Block does not exist", graphql_value!(None)))
+        }
+    }
+
+    field tip(&executor) -> FieldResult<Block> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+        match chain.tip() {
+            Some(block) => Ok(block.clone()),
+            None => Err(juniper::FieldError::new("Chain has no blocks", graphql_value!(None)))
+        }
+    }
+
+    field height(&executor) -> FieldResult<U64> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+        match chain.tip() {
+            Some(block) => Ok(U64(block.inner.read().unwrap().id)),
+            None => Err(juniper::FieldError::new("Chain has no blocks", graphql_value!(None)))
+        }
+    }
+
+    field blockHash(&executor, height: U64) -> FieldResult<String> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+        match chain.main_chain_block(height.0 as usize) {
+            Some(block) => Ok(hex::encode_upper(&block.hash())),
+            None => Err(juniper::FieldError::new("Block does not exist", graphql_value!(None)))
+        }
+    }
+
+    field blocks(&executor, start: U64, len: U64) -> FieldResult<Vec<Block>> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+        let end = match start.0.checked_add(len.0) {
+            Some(end) if end <= chain.main_chain.len() as u64 => end,
+            _ => {
+                return Err(juniper::FieldError::new("Block does not exist", graphql_value!(None)));
+            }
+        };
+        Ok(chain.main_chain[start.0 as usize..end as usize].iter()
+            .map(|index| chain.blocks[*index].clone())
+            .collect())
+    }
+
+    field transactionProof(&executor, block_id: U64, index: U64) -> FieldResult<merkle::MerkleProof> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+        let block = match chain.main_chain_block(block_id.0 as usize) {
+            Some(block) => block,
+            None => {
+                return Err(juniper::FieldError::new("Block does not exist", graphql_value!(None)));
+            }
+        };
+        match block.data_proof(index.0 as usize) {
+            Some(proof) => Ok(proof),
+            None => Err(juniper::FieldError::new("Data does not exist", graphql_value!(None)))
+        }
+    }
+
+    field transaction(&executor, id: String) -> FieldResult<Transaction> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+        let id = match hex::decode(id) {
+            Ok(ref id) if id.len() == 32 => sha256hash_from_slice(id),
+            _ => {
+                return Err(juniper::FieldError::new("Invalid transaction id", graphql_value!(None)));
+            }
+        };
+        match chain.transaction(&id) {
+            Some(transaction) => Ok(transaction),
+            None => Err(juniper::FieldError::new("Transaction does not exist", graphql_value!(None)))
+        }
+    }
+
+    field transactionStatus(&executor, id: String) -> FieldResult<TransactionStatus> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+        let id = match hex::decode(id) {
+            Ok(ref id) if id.len() == 32 => sha256hash_from_slice(id),
+            _ => {
+                return Err(juniper::FieldError::new("Invalid transaction id", graphql_value!(None)));
+            }
+        };
+        match chain.transaction_status(&id) {
+            Some(status) => Ok(status),
+            None => Err(juniper::FieldError::new("Transaction does not exist", graphql_value!(None)))
+        }
+    }
+
+    field balance(&executor, address: String) -> FieldResult<U64> {
+        let address_vec: Vec<u8>;
+
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+
+        match hex::decode(address) {
+            Ok(data) => {
+                address_vec = data;
+            }
+            Err(e) => {
+                return Err(juniper::FieldError::new("Invalid hex address", graphql_value!(None)));
+            }
+        }
+
+        if address_vec.len() != 32 {
+            return Err(juniper::FieldError::new("Invalid length address", graphql_value!(None)));
+        }
+
+        Ok(U64(chain.ledger.balance(&sha256hash_from_slice(&address_vec))))
+    }
+
+    field nextNonce(&executor, address: String) -> FieldResult<U64> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+
+        let address = match hex::decode(address) {
+            Ok(ref address) if address.len() == 32 => sha256hash_from_slice(address),
+            _ => {
+                return Err(juniper::FieldError::new("Invalid address", graphql_value!(None)));
+            }
+        };
+        Ok(U64(chain.mempool.next_nonce(&address, &chain.ledger)))
+    }
+
+    field addressTransactions(&executor, address: String, after: Option<String>, first: Option<i32>)
+                              -> FieldResult<history::AddressTransactionConnection> {
+        let context = executor.context();
+        let chain = context.blockchain.read().unwrap();
+
+        let address = match hex::decode(address) {
+            Ok(ref address) if address.len() == 32 => sha256hash_from_slice(address),
+            _ => {
+                return Err(juniper::FieldError::new("Invalid address", graphql_value!(None)));
+            }
+        };
+        let after = match after {
+            Some(after) => match hex::decode(after) {
+                Ok(ref after) if after.len() == 32 => Some(sha256hash_from_slice(after)),
+                _ => {
+                    return Err(juniper::FieldError::new("Invalid cursor", graphql_value!(None)));
+                }
+            },
+            None => None
+        };
+        let first = match first {
+            Some(first) if first < 0 || first as usize > history::MAX_PAGE_SIZE => {
+                return Err(juniper::FieldError::new(
+                    format!("first must be between 0 and {}", history::MAX_PAGE_SIZE), graphql_value!(None)));
+            }
+            Some(first) => first as usize,
+            None => history::DEFAULT_PAGE_SIZE
+        };
+        match history::address_transactions(&chain, &address, after.as_ref(), first) {
+            Ok(connection) => Ok(connection),
+            Err(e) => Err(juniper::FieldError::new(e, graphql_value!(None)))
+        }
+    }
+
+    field richList(&executor, limit: i32) -> FieldResult<Vec<ledger::AccountBalance>> {
         let context = executor.context();
         let chain = context.blockchain.read().unwrap();
-        if (start+len) as usize > chain.blocks.len() {
-            return Err(juniper::FieldError::new("Block does not exist", graphql_value!(None)))
+        if limit < 0 {
+            return Err(juniper::FieldError::new("Invalid limit", graphql_value!(None)))
         }
-        Ok(chain.blocks[start as usize..(start+len) as usize].iter().cloned().collect())
+        Ok(chain.ledger.rich_list(limit as usize))
     }
 });
 
 struct Mutation;
 
 graphql_object!(Mutation: Context |&self| {
-    field publishTransaction(&executor, from: String, to: String, amount: i32) -> FieldResult<Transaction> {
-        let from_vec: Vec<u8>;
+    field publishTransaction(&executor, public_key: String, to: String, amount: U64, fee: U64, nonce: U64,
+                             signature: String) -> FieldResult<Transaction> {
+        let public_key_vec: Vec<u8>;
         let to_vec: Vec<u8>;
+        let signature_vec: Vec<u8>;
 
         let context = executor.context();
-        let mut chain = context.blockchain.write().unwrap();
 
-        match hex::decode(from) {
+        match hex::decode(public_key) {
             Ok(data) => {
-                from_vec = data;
+                public_key_vec = data;
             }
             Err(e) => {
-                return Err(juniper::FieldError::new("Invalid hex from address", graphql_value!(None)));
+                return Err(juniper::FieldError::new("Invalid hex public key", graphql_value!(None)));
             }
         }
         match hex::decode(to) {
@@ -467,24 +1322,94 @@ graphql_object!(Mutation: Context |&self| {
                 return Err(juniper::FieldError::new("Invalid hex to address", graphql_value!(None)));
             }
         }
+        match hex::decode(signature) {
+            Ok(data) => {
+                signature_vec = data;
+            }
+            Err(e) => {
+                return Err(juniper::FieldError::new("Invalid hex signature", graphql_value!(None)));
+            }
+        }
 
-        if from_vec.len() != 32 {
-            return Err(juniper::FieldError::new("Invalid length from address", graphql_value!(None)));
+        if public_key_vec.len() != 32 {
+            return Err(juniper::FieldError::new("Invalid length public key", graphql_value!(None)));
         }
         if to_vec.len() != 32 {
             return Err(juniper::FieldError::new("Invalid length to address", graphql_value!(None)));
         }
+        if signature_vec.len() != 64 {
+            return Err(juniper::FieldError::new("Invalid length signature", graphql_value!(None)));
+        }
+        if amount.0 == 0 {
+            return Err(juniper::FieldError::new("Amount must be greater than zero", graphql_value!(None)));
+        }
 
+        let public_key = sha256hash_from_slice(&public_key_vec);
         let transaction = Transaction {
-            sender: sha256hash_from_slice(&from_vec),
+            sender: address_from_public_key(&public_key),
             recipient: sha256hash_from_slice(&to_vec),
-            amount: amount as u64,
+            amount: amount.0,
+            fee: fee.0,
+            nonce: nonce.0,
+            public_key,
+            signature: signature_vec,
         };
 
-        chain.add_data(Box::new(transaction.clone()));
+        // The lock is let go before announcing, so a slow peer doesn't hold up the rest of the node
+        let result = context.blockchain.write().unwrap().add_transaction(transaction.clone());
+        match result {
+            Ok(_) => {}
+            Err(e) => {
+                return Err(juniper::FieldError::new(format!("Transaction rejected: {}", e), graphql_value!(None)));
+            }
+        }
+        context.network.announce_transaction(&transaction);
 
         Ok(transaction)
     }
+
+    field generateBlocks(&executor, count: i32) -> FieldResult<Vec<Block>> {
+        let context = executor.context();
+        let params = context.blockchain.read().unwrap().params;
+        if !params.on_demand_mining {
+            return Err(juniper::FieldError::new(
+                format!("Blocks can't be generated on demand on {}", params.name), graphql_value!(None)
+            ));
+        }
+        if count < 0 {
+            return Err(juniper::FieldError::new("Invalid count", graphql_value!(None)));
+        }
+
+        let mut blocks = vec![];
+        for _ in 0..count {
+            let candidate = context.blockchain.read().unwrap().candidate_block(&context.miner_address);
+            let block = match candidate {
+                Some(block) => block,
+                None => {
+                    return Err(juniper::FieldError::new("Chain has no tip to build on", graphql_value!(None)));
+                }
+            };
+            match block.mine() {
+                Some(nonce) => {
+                    block.inner.write().unwrap().nonce = nonce;
+                }
+                None => {
+                    return Err(juniper::FieldError::new("Failed to mine block", graphql_value!(None)));
+                }
+            }
+            let result = context.blockchain.write().unwrap().add_block(block.clone());
+            match result {
+                Ok(_) => {}
+                Err(e) => {
+                    return Err(juniper::FieldError::new(format!("Generated block is invalid: {}", e),
+                                                        graphql_value!(None)));
+                }
+            }
+            context.network.announce_block(&block);
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
 });
 
 type Schema = juniper::RootNode<'static, Query, Mutation>;
@@ -493,36 +1418,114 @@ fn schema() -> Schema {
     Schema::new(Query, Mutation)
 }
 
+fn transaction_to_pb(transaction: &Transaction) -> chain::Transaction {
+    let mut msg = chain::Transaction::new();
+    msg.set_from(transaction.sender.to_vec());
+    msg.set_to(transaction.recipient.to_vec());
+    msg.set_amount(transaction.amount);
+    msg.set_fee(transaction.fee);
+    msg.set_nonce(transaction.nonce);
+    msg.set_public_key(transaction.public_key.to_vec());
+    msg.set_signature(transaction.signature.clone());
+    msg
+}
+
+fn pb_to_transaction(msg: &chain::Transaction) -> Transaction {
+    Transaction {
+        sender: sha256hash_from_slice(msg.get_from()),
+        recipient: sha256hash_from_slice(msg.get_to()),
+        amount: msg.get_amount(),
+        fee: msg.get_fee(),
+        nonce: msg.get_nonce(),
+        public_key: sha256hash_from_slice(msg.get_public_key()),
+        signature: msg.get_signature().to_vec(),
+    }
+}
+
+fn block_header_to_pb(block: &Block) -> chain::BlockHeader {
+    let inner = block.inner.read().unwrap();
+    let mut header_msg = chain::BlockHeader::new();
+    header_msg.set_id(inner.id);
+    header_msg.set_timestamp(inner.timestamp);
+    header_msg.set_nonce(inner.nonce);
+    header_msg.set_prev_block_hash(inner.prev_block_hash.to_vec());
+    header_msg.set_difficulty(inner.difficulty as u32);
+    header_msg.set_merkle_root(inner.merkle_root.to_vec());
+    header_msg
+}
+
+/// A block with no data, only useful for checking the header's hash
+fn pb_header_to_block(msg: &chain::BlockHeader) -> Block {
+    Block::restore(
+        &vec![],
+        sha256hash_from_slice(msg.get_prev_block_hash()),
+        sha256hash_from_slice(msg.get_merkle_root()),
+        msg.get_id(),
+        msg.get_timestamp(),
+        msg.get_nonce(),
+        msg.get_difficulty() as u8,
+    )
+}
+
+fn data_to_pb(data: &Box<BlockData>) -> chain::Block_Data {
+    let mut block_data_msg = chain::Block_Data::new();
+    match data.as_binary_data() {
+        Some(data) => {
+            let mut data_msg = chain::BinaryData::new();
+            data_msg.set_data(data.data.clone());
+            block_data_msg.set_field_type(chain::Block_DataType::BINARY_DATA);
+            block_data_msg.set_binaryData(data_msg);
+            return block_data_msg;
+        }
+        None => {}
+    };
+    match data.as_transaction() {
+        Some(data) => {
+            block_data_msg.set_field_type(chain::Block_DataType::TRANSACTION);
+            block_data_msg.set_transaction(transaction_to_pb(data));
+            return block_data_msg;
+        }
+        None => {}
+    };
+    match data.as_coinbase() {
+        Some(data) => {
+            let mut data_msg = chain::Coinbase::new();
+            data_msg.set_recipient(data.recipient.to_vec());
+            data_msg.set_amount(data.amount);
+            data_msg.set_height(data.height);
+            block_data_msg.set_field_type(chain::Block_DataType::COINBASE);
+            block_data_msg.set_coinbase(data_msg);
+        }
+        None => {}
+    };
+    block_data_msg
+}
+
+fn pb_to_data(msg: &chain::Block_Data) -> Box<BlockData> {
+    match msg.get_field_type() {
+        chain::Block_DataType::BINARY_DATA => {
+            Box::new(BinaryData {
+                data: msg.get_binaryData().get_data().to_vec()
+            })
+        }
+        chain::Block_DataType::TRANSACTION => {
+            Box::new(pb_to_transaction(msg.get_transaction()))
+        }
+        chain::Block_DataType::COINBASE => {
+            Box::new(Coinbase {
+                recipient: sha256hash_from_slice(msg.get_coinbase().get_recipient()),
+                amount: msg.get_coinbase().get_amount(),
+                height: msg.get_coinbase().get_height(),
+            })
+        }
+    }
+}
+
 fn block_to_pb(block: &Block) -> chain::Block {
     let mut block_data = protobuf::RepeatedField::<chain::Block_Data>::default();
     let inner = block.inner.read().unwrap();
     for data in inner.data.iter() {
-        match data.as_binary_data() {
-            Some(data) => {
-                let mut data_msg = chain::BinaryData::new();
-                data_msg.set_data(data.data.clone());
-                let mut block_data_msg = chain::Block_Data::new();
-                block_data_msg.set_field_type(chain::Block_DataType::BINARY_DATA);
-                block_data_msg.set_binaryData(data_msg);
-                block_data.push(block_data_msg);
-                continue;
-            }
-            None => {}
-        };
-        match data.as_transaction() {
-            Some(data) => {
-                let mut data_msg = chain::Transaction::new();
-                data_msg.set_from(data.sender.to_vec());
-                data_msg.set_to(data.recipient.to_vec());
-                data_msg.set_amount(data.amount);
-                let mut block_data_msg = chain::Block_Data::new();
-                block_data_msg.set_field_type(chain::Block_DataType::TRANSACTION);
-                block_data_msg.set_transaction(data_msg);
-                block_data.push(block_data_msg);
-                continue;
-            }
-            None => {}
-        };
+        block_data.push(data_to_pb(data));
     }
 
     let mut block_msg = chain::Block::new();
@@ -530,49 +1533,26 @@ fn block_to_pb(block: &Block) -> chain::Block {
     block_msg.set_timestamp(inner.timestamp);
     block_msg.set_nonce(inner.nonce);
     block_msg.set_prev_block_hash(inner.prev_block_hash.to_vec());
+    block_msg.set_merkle_root(inner.merkle_root.to_vec());
+    block_msg.set_difficulty(inner.difficulty as u32);
     block_msg.set_data(block_data);
     block_msg
 }
 
 fn pb_to_block(msg: &chain::Block) -> Block {
-    let mut block_data: Vec<Box<BlockData>> = vec![];
-    for data in msg.get_data().iter() {
-        block_data.push(match data.get_field_type() {
-            chain::Block_DataType::BINARY_DATA => {
-                Box::new(BinaryData {
-                    data: data.get_binaryData().get_data().to_vec()
-                })
-            }
-            chain::Block_DataType::TRANSACTION => {
-                Box::new(Transaction {
-                    sender: sha256hash_from_slice(data.get_transaction().get_from()),
-                    recipient: sha256hash_from_slice(data.get_transaction().get_to()),
-                    amount: data.get_transaction().get_amount(),
-                })
-            }
-        })
-    }
+    let block_data: Vec<Box<BlockData>> = msg.get_data().iter().map(pb_to_data).collect();
 
     Block::restore(
         &block_data,
         sha256hash_from_slice(msg.get_prev_block_hash()),
+        sha256hash_from_slice(msg.get_merkle_root()),
         msg.get_id(),
         msg.get_timestamp(),
         msg.get_nonce(),
+        msg.get_difficulty() as u8,
     )
 }
 
-fn write_pb_block(block: &chain::Block, hash: Sha256Hash) -> protobuf::error::ProtobufResult<()> {
-    match fs::File::create(format!("./blocks/block{}", hex::encode(hash))) {
-        Ok(mut out) => {
-            block.write_to_writer(&mut out)
-        }
-        Err(e) => {
-            Err(protobuf::error::ProtobufError::IoError(e))
-        }
-    }
-}
-
 fn read_pb_block(path: &path::Path) -> io::Result<chain::Block> {
     let mut f = fs::File::open(path)?;
     match protobuf::parse_from_reader::<chain::Block>(&mut f) {
@@ -585,61 +1565,10 @@ fn read_pb_block(path: &path::Path) -> io::Result<chain::Block> {
     }
 }
 
-fn start_db_thread(blockchain: Arc<RwLock<Blockchain>>) -> (Arc<RwLock<bool>>, Arc<RwLock<bool>>) {
-    let (tx, rx) = mpsc::channel();
-    let exit_flag = Arc::new(RwLock::new(false));
-    let exited_flag = Arc::new(RwLock::new(false));
-
-    let exited_flag_2 = Arc::clone(&exited_flag);
-    thread::spawn(move || {
-        loop {
-            match rx.recv() {
-                Ok(_) => {
-                    println!("Syncing blocks to db");
-                    for block in blockchain.read().unwrap().blocks.iter() {
-                        println!("{:?}", block);
-                        let block_msg = block_to_pb(block);
-                        match write_pb_block(&block_msg, block.hash()) {
-                            Ok(_) => {}
-                            Err(e) => {
-                                panic!("Failed to write block to fs: {}", e)
-                            }
-                        }
-                    }
-                }
-                Err(_) => {
-                    *exited_flag_2.write().unwrap() = true;
-                    break;
-                }
-            }
-        }
-    });
-
-    let exit_flag_2 = Arc::clone(&exit_flag);
-    thread::spawn(move|| {
-        let timer = Timer::new();
-        let guard = {
-            let timer_tx = mpsc::Sender::clone(&tx);
-            timer.schedule_repeating(chrono::Duration::seconds(5), move || {
-                timer_tx.send("sync".to_string()).unwrap();
-            })
-        };
-        loop {
-            if *exit_flag_2.read().unwrap() {
-                drop(guard);
-                tx.send("sync".to_string()).unwrap();
-                break;
-            }
-        }
-    });
-
-    (exit_flag, exited_flag)
-}
-
-fn find_block_files() -> io::Result<Vec<path::PathBuf>> {
+fn find_block_files(data_dir: &str) -> io::Result<Vec<path::PathBuf>> {
     let re = Regex::new(r"^block[0-9a-fA-F]+$").unwrap();
 
-    let db_path = path::Path::new("./blocks");
+    let db_path = path::Path::new(data_dir);
     fs::create_dir_all(db_path)?;
     let dir = fs::read_dir(db_path)?;
     let mut files: Vec<_> = vec![];
@@ -665,72 +1594,362 @@ fn find_block_files() -> io::Result<Vec<path::PathBuf>> {
     Ok(files)
 }
 
-fn init_db() -> io::Result<Blockchain> {
-    let mut chain = Blockchain::new();
-    let files = find_block_files()?;
-
-    for block in files.iter() {
-        let block = pb_to_block(&read_pb_block(block)?);
-        if block.is_valid(GENESIS_DIFFICULTY) {
-            let hash = block.hash();
-            chain.blocks.push(block);
-            chain.hash_index_map.insert(hash, (chain.blocks.len()-1) as i64);
-        } else {
-            println!("Encountered invalid block!");
+/// Adds stored or imported blocks to the chain, returning how many were rejected. Fails if the blocks are built
+/// on a different genesis block.
+fn load_blocks(chain: &mut Blockchain, mut blocks: Vec<Block>) -> io::Result<usize> {
+    let genesis_hash = chain.blocks[0].hash();
+    blocks.sort_by_key(|block| block.inner.read().unwrap().id);
+
+    let mut rejected = 0;
+    for block in blocks {
+        let id = block.inner.read().unwrap().id;
+        let hash = block.hash();
+        if id == 0 {
+            if hash != genesis_hash {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          format!("Chain starts from genesis block {} instead of {}",
+                                                  hex::encode(hash), hex::encode(genesis_hash))));
+            }
+            continue;
+        }
+        match chain.add_block(block) {
+            Ok(_) | Err(validation::BlockError::AlreadyKnown) => {}
+            Err(e) => {
+                println!("Rejected block #{} {}: {}", id, hex::encode(hash), e);
+                rejected += 1;
+            }
         }
     }
+    Ok(rejected)
+}
+
+/// Loads the stored chain, returning how many stored blocks were invalid or corrupt. Every block the chain accepts
+/// afterwards is written to the store.
+fn init_db(config: &config::Config) -> io::Result<(Blockchain, usize)> {
+    load_db(config, false)
+}
 
-    for block in chain.blocks.iter() {
-        let mut inner = block.inner.write().unwrap();
-        match chain.hash_index_map.get(&inner.prev_block_hash) {
-            Some(index) => {
-                inner.prev_block_index = *index
+/// Loads the stored chain like `init_db` without changing anything in the data directory. Corrupt data is left
+/// where it is and blocks the chain accepts afterwards aren't stored.
+fn read_db(config: &config::Config) -> io::Result<(Blockchain, usize)> {
+    load_db(config, true)
+}
+
+fn load_db(config: &config::Config, read_only: bool) -> io::Result<(Blockchain, usize)> {
+    let mut chain = Blockchain::new(config.params);
+    chain.init_genesis();
+    let mut store = if read_only {
+        store::BlockStore::open_read_only(&config.data_dir)?
+    } else {
+        store::BlockStore::open(&config.data_dir)?
+    };
+    let blocks = store.blocks()?;
+    let mut rejected = load_blocks(&mut chain, blocks)?;
+
+    // Older versions wrote each block to its own file, those are moved into the store
+    let mut legacy_files = vec![];
+    let mut legacy_blocks = vec![];
+    for file in find_block_files(&config.data_dir)? {
+        match read_pb_block(&file) {
+            Ok(ref msg) if p2p::well_formed_block(msg) => {
+                let block = pb_to_block(msg);
+                legacy_files.push((file, block.hash()));
+                legacy_blocks.push(block);
             }
-            None => {}
+            _ => store.quarantine_file(&file)?
         }
     }
+    rejected += load_blocks(&mut chain, legacy_blocks)?;
 
-    if chain.blocks.len() == 0 {
-        chain.init_genesis();
-        return Ok(chain);
+    let quarantined = store.quarantined();
+    if read_only {
+        if quarantined > 0 {
+            println!("Found {} corrupt blocks, left in place as the data directory is only being read", quarantined);
+        }
+        return Ok((chain, rejected + quarantined));
+    }
+    if quarantined > 0 {
+        println!("Quarantined {} corrupt blocks, see {}", quarantined,
+                 path::Path::new(&config.data_dir).join("quarantine").display());
+    }
+
+    let pending = store.load_mempool()?;
+    let saved = pending.len();
+    let dropped = chain.restore_pending(pending);
+    if saved > 0 {
+        println!("Restored {} pending transactions, dropped {} that were mined or are no longer valid",
+                 saved - dropped, dropped);
+    }
+    chain.attach_store(store)?;
+    for (file, hash) in legacy_files {
+        if chain.hash_index_map.contains_key(&hash) {
+            fs::remove_file(file)?;
+        }
     }
 
-    Ok(chain)
+    Ok((chain, rejected + quarantined))
 }
 
-fn main() {
-    let log = warp::log("warp_server");
+fn init_chain(config: &config::Config) -> io::Result<()> {
+    let (chain, _) = init_db(config)?;
+    println!("Initialised {} chain in {} with {} blocks", config.params.name, config.data_dir, chain.main_chain.len());
+    Ok(())
+}
 
-    let chain;
-    match init_db() {
-        Ok(blockchain) => {
-            chain = Arc::new(RwLock::new(blockchain));
-        }
-        Err(e) => {
-            panic!("Cannot initialise chain: {}", e);
+fn verify_chain(config: &config::Config) -> io::Result<()> {
+    let (chain, rejected) = read_db(config)?;
+    println!("Verified {} blocks, {} on the main chain, {} rejected",
+             chain.blocks.len() + rejected, chain.main_chain.len(), rejected);
+    if rejected != 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} stored blocks are invalid", rejected)));
+    }
+    Ok(())
+}
+
+/// Writes the main chain as a sequence of length delimited protobuf blocks
+fn export_chain(config: &config::Config, file: &str) -> io::Result<()> {
+    let (chain, _) = read_db(config)?;
+    let mut out = fs::File::create(file)?;
+    for index in chain.main_chain.iter() {
+        block_to_pb(&chain.blocks[*index]).write_length_delimited_to_writer(&mut out)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    }
+    out.sync_all()?;
+    println!("Exported {} blocks to {}", chain.main_chain.len(), file);
+    Ok(())
+}
+
+fn import_chain(config: &config::Config, file: &str) -> io::Result<()> {
+    let (mut chain, _) = init_db(config)?;
+    let known = chain.blocks.len();
+
+    let mut f = fs::File::open(file)?;
+    let mut input = protobuf::CodedInputStream::new(&mut f);
+    let mut blocks = vec![];
+    while !input.eof().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))? {
+        let block_msg: chain::Block = input.read_message()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if !p2p::well_formed_block(&block_msg) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "malformed block"));
         }
+        blocks.push(pb_to_block(&block_msg));
     }
+    let rejected = load_blocks(&mut chain, blocks)?;
+    println!("Imported {} new blocks from {}, {} rejected", chain.blocks.len() - known, file, rejected);
+    Ok(())
+}
 
-    chain.write().unwrap().add_block();
+fn run_node(config: config::Config) -> io::Result<()> {
+    let log = warp::log("warp_server");
+    println!("Running on {}", config.params.name);
 
-    let (exit_flag, exited_flag) = start_db_thread(chain.clone());
+    let (chain, _) = init_db(&config)?;
+    let chain = Arc::new(RwLock::new(chain));
 
+    let miner_address = match config.miner_address {
+        Some(address) => address,
+        None => {
+            println!("No miner address set, block rewards will be unspendable");
+            Sha256Hash::default()
+        }
+    };
+
+    let miner: Arc<RwLock<Option<miner::Miner>>> = Arc::new(RwLock::new(None));
+
+    let miner_2 = Arc::clone(&miner);
+    let chain_2 = Arc::clone(&chain);
     ctrlc::set_handler(move || {
-        *exit_flag.write().unwrap() = true;
-        while !*exited_flag.read().unwrap() {}
+        match *miner_2.read().unwrap() {
+            Some(ref miner) => miner.stop(),
+            None => {}
+        }
+        // Blocks are stored while the chain is locked, so this waits for any write in progress
+        let chain = chain_2.write().unwrap();
+        chain.save_pending();
         std::process::exit(0);
     }).expect("Error setting Ctrl-C handler");
 
+    // Saving reads the chain without locking out readers, and is skipped when the mempool hasn't changed
+    let chain_3 = Arc::clone(&chain);
+    thread::spawn(move || {
+        let mut saved_version = chain_3.read().unwrap().mempool_version;
+        loop {
+            thread::sleep(time::Duration::from_secs(PENDING_SAVE_INTERVAL));
+            let chain = chain_3.read().unwrap();
+            if chain.mempool_version != saved_version {
+                chain.save_pending();
+                saved_version = chain.mempool_version;
+            }
+        }
+    });
+
+    let mut peers = config.peers.clone();
+    match config.sync_peer {
+        Some(sync_peer) if !peers.contains(&sync_peer) => {
+            peers.push(sync_peer);
+        }
+        _ => {}
+    }
+
+    let network = p2p::Network::start(chain.clone(), SocketAddr::new(config.bind_address, config.p2p_port), peers)?;
+
+    // Peers and the API are served while syncing, only mining waits for it so blocks aren't mined on a stale tip
+    let chain_4 = Arc::clone(&chain);
+    let network_2 = network.clone();
+    let sync_peer = config.sync_peer;
+    let start_miner = !config.params.on_demand_mining;
+    let mining_threads = config.mining_threads;
+    thread::spawn(move || {
+        match sync_peer {
+            Some(sync_peer) => sync::sync_until_done(&chain_4, sync_peer),
+            None => {}
+        }
+        if start_miner {
+            *miner.write().unwrap() = Some(miner::Miner::start(chain_4, miner_address, network_2, mining_threads));
+        }
+    });
+
     let state = warp::any().map(move || Context {
         blockchain: chain.clone(),
+        network: network.clone(),
+        miner_address,
     });
-    let graphql_filter = juniper_warp::make_graphql_filter(schema(), state.boxed());
+    let graphql_filter = juniper_warp::make_graphql_filter(schema(), state.clone().boxed());
+    let subscriptions = warp::path("subscriptions")
+        .and(warp::ws2())
+        .and(state.clone())
+        .map(|ws: warp::ws::Ws2, context: Context| {
+            let reply = ws.on_upgrade(move |socket| subscription::serve(socket, context));
+            warp::reply::with_header(reply, "sec-websocket-protocol", subscription::PROTOCOL)
+        });
+    let rpc = warp::post2()
+        .and(warp::path("rpc"))
+        .and(warp::body::content_length_limit(rpc::MAX_REQUEST_SIZE))
+        .and(warp::body::concat())
+        .and(state)
+        .map(|body: warp::body::FullBody, context: Context| rpc::reply(&context, body.bytes()));
 
     warp::serve(
         warp::get2()
             .and(warp::path("graphql"))
             .and(juniper_warp::graphiql_handler("/graphql"))
+            .or(subscriptions)
+            .or(rpc)
             .or(warp::path("graphql").and(graphql_filter))
             .with(log),
-    ).run(([127, 0, 0, 1], 3000));
-}
\ No newline at end of file
+    ).run(SocketAddr::new(config.bind_address, config.graphql_port));
+    Ok(())
+}
+
+fn main() {
+    let matches = config::cli().get_matches();
+    let (command, command_matches) = matches.subcommand();
+    let command_matches = command_matches.unwrap_or(&matches);
+
+    let config = match config::Config::from_matches(command_matches) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let result = match command {
+        "init" => init_chain(&config),
+        "verify" => verify_chain(&config),
+        "export" => export_chain(&config, command_matches.value_of("file").unwrap()),
+        "import" => import_chain(&config, command_matches.value_of("file").unwrap()),
+        _ => run_node(config),
+    };
+    match result {
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_transaction(seed: u8, recipient: Sha256Hash, amount: u64, fee: u64, nonce: u64) -> Transaction {
+        let (secret_key, public_key) = ed25519::keypair(&[seed; 32]);
+        let mut transaction = Transaction {
+            sender: address_from_public_key(&public_key),
+            recipient,
+            amount,
+            fee,
+            nonce,
+            public_key,
+            signature: vec![],
+        };
+        transaction.signature = ed25519::signature(&transaction.signing_data(), &secret_key).to_vec();
+        transaction
+    }
+
+    /// Mines a block on top of any block in the tree, not just the tip
+    fn mine_on(chain: &Blockchain, parent: usize, transactions: &[Transaction], miner: Sha256Hash) -> Block {
+        let height = chain.blocks[parent].inner.read().unwrap().id + 1;
+        let fees: u64 = transactions.iter().map(|transaction| transaction.fee).sum();
+        let mut data = vec![Coinbase::new(miner, height, chain.params.block_reward(height) + fees)];
+        data.extend(transactions.iter().map(|transaction| Box::new(transaction.clone()) as Box<BlockData>));
+        let block = chain.blocks[parent].next_block(parent as i64, &data, chain.next_difficulty(parent));
+        {
+            let mut inner = block.inner.write().unwrap();
+            inner.timestamp = inner.timestamp.max(validation::median_time_past(chain, parent) + 1);
+        }
+        let nonce = block.mine().unwrap();
+        block.inner.write().unwrap().nonce = nonce;
+        block
+    }
+
+    #[test]
+    fn switching_branches_moves_balances_indexes_and_mempool() {
+        let mut chain = Blockchain::new(&params::REGTEST);
+        chain.init_genesis();
+        let alice = address_from_public_key(&ed25519::keypair(&[1; 32]).1);
+        let (bob, dave, erin, miner) = ([2; 32], [4; 32], [5; 32], [9; 32]);
+        let reward = chain.params.block_reward(1);
+
+        let common = mine_on(&chain, 0, &[], alice);
+        chain.add_block(common).unwrap();
+        let to_bob = signed_transaction(1, bob, 100, 1, 0);
+        let to_erin = signed_transaction(1, erin, 50, 1, 1);
+        let branch_a = mine_on(&chain, 1, &[to_bob.clone(), to_erin.clone()], miner);
+        chain.add_block(branch_a).unwrap();
+        assert_eq!(chain.main_chain, vec![0, 1, 2]);
+        assert_eq!((chain.ledger.balance(&bob), chain.ledger.nonce(&alice)), (100, 2));
+
+        // The competing branch spends alice's first nonce differently and overtakes on its second block
+        let to_dave = signed_transaction(1, dave, 70, 2, 0);
+        let branch_b = mine_on(&chain, 1, &[to_dave.clone()], miner);
+        chain.add_block(branch_b).unwrap();
+        assert_eq!(chain.main_chain, vec![0, 1, 2]);
+        let branch_b = mine_on(&chain, 3, &[], miner);
+        chain.add_block(branch_b).unwrap();
+        assert_eq!(chain.main_chain, vec![0, 1, 3, 4]);
+
+        assert_eq!(chain.ledger.balance(&alice), reward - 70 - 2);
+        assert_eq!(chain.ledger.balance(&bob), 0);
+        assert_eq!(chain.ledger.balance(&erin), 0);
+        assert_eq!(chain.ledger.balance(&dave), 70);
+        assert_eq!(chain.ledger.balance(&miner), 2 * reward + 2);
+        assert_eq!(chain.ledger.nonce(&alice), 1);
+
+        assert!(chain.transaction_index.contains_key(&to_dave.hash()));
+        assert!(!chain.transaction_index.contains_key(&to_bob.hash()));
+        assert!(!chain.transaction_index.contains_key(&to_erin.hash()));
+        assert_eq!(chain.address_index[&alice], vec![to_dave.hash()]);
+        assert_eq!(chain.address_index[&dave], vec![to_dave.hash()]);
+        assert!(!chain.address_index.contains_key(&bob));
+        assert!(!chain.address_index.contains_key(&erin));
+
+        // The transfer to erin is still valid after the transfer to dave, the one to bob reuses its nonce
+        assert!(chain.mempool.get(&to_bob.hash()).is_none());
+        assert!(chain.mempool.get(&to_erin.hash()).is_some());
+        assert_eq!(chain.mempool.len(), 1);
+        assert_eq!(chain.mempool.next_nonce(&alice, &chain.ledger), 2);
+    }
+}
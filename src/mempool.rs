@@ -0,0 +1,431 @@
+use std::fmt;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use ledger::Ledger;
+use {BlockData, Sha256Hash, Transaction};
+
+/// Most bytes of transactions held at once
+pub const MAX_MEMPOOL_SIZE: usize = 8 * 1024 * 1024;
+/// Most transactions held at once
+pub const MAX_MEMPOOL_TRANSACTIONS: usize = 20000;
+/// Most transactions a single sender can have waiting to be mined
+pub const MAX_SENDER_TRANSACTIONS: usize = 100;
+
+#[derive(Debug)]
+pub enum MempoolError {
+    AlreadyKnown,
+    ZeroAmount,
+    InvalidSignature,
+    StaleNonce { expected: u64, actual: u64 },
+    NonceGap { expected: u64, actual: u64 },
+    ReplacementFeeTooLow { fee: u64, replaced_fee: u64 },
+    TooManyFromSender,
+    InsufficientBalance { available: u64, required: u64 },
+    FeeRateTooLow,
+}
+
+impl fmt::Display for MempoolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MempoolError::AlreadyKnown => write!(f, "transaction is already pending"),
+            MempoolError::ZeroAmount => write!(f, "amount must be greater than zero"),
+            MempoolError::InvalidSignature => write!(f, "invalid signature"),
+            MempoolError::StaleNonce { expected, actual } =>
+                write!(f, "nonce {} has already been used, the next nonce is {}", actual, expected),
+            MempoolError::NonceGap { expected, actual } =>
+                write!(f, "nonce {} is ahead of the next nonce {}", actual, expected),
+            MempoolError::ReplacementFeeTooLow { fee, replaced_fee } =>
+                write!(f, "fee {} must be higher than the fee {} of the pending transaction with the same nonce",
+                       fee, replaced_fee),
+            MempoolError::TooManyFromSender =>
+                write!(f, "sender already has {} pending transactions", MAX_SENDER_TRANSACTIONS),
+            MempoolError::InsufficientBalance { available, required } =>
+                write!(f, "sender can spend {} but the amount and fee come to {}", available, required),
+            MempoolError::FeeRateTooLow => write!(f, "mempool is full of transactions paying a higher fee rate"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    hash: Sha256Hash,
+    transaction: Transaction,
+    size: usize,
+}
+
+impl Entry {
+    /// Compares fee per byte without dividing
+    fn cmp_fee_rate(&self, other: &Entry) -> Ordering {
+        (self.transaction.fee as u128 * other.size as u128)
+            .cmp(&(other.transaction.fee as u128 * self.size as u128))
+    }
+}
+
+/// The next transaction from a sender that could go in a block
+struct Candidate<'a> {
+    entries: &'a [&'a Entry],
+    position: usize,
+}
+
+impl<'a> Candidate<'a> {
+    fn entry(&self) -> &'a Entry {
+        self.entries[self.position]
+    }
+}
+
+impl<'a> Ord for Candidate<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.entry().cmp_fee_rate(other.entry()).then_with(|| other.entry().hash.cmp(&self.entry().hash))
+    }
+}
+
+impl<'a> PartialOrd for Candidate<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for Candidate<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for Candidate<'a> {}
+
+/// Transactions waiting to be mined. Each sender's transactions always run on from the sender's next nonce in the
+/// ledger without gaps, and are affordable together, so any prefix of them can go in the next block.
+#[derive(Debug)]
+pub struct Mempool {
+    entries: HashMap<Sha256Hash, Entry>,
+    /// Hashes of each sender's transactions by nonce
+    senders: HashMap<Sha256Hash, BTreeMap<u64, Sha256Hash>>,
+    /// Total bytes of every transaction held
+    size: usize,
+}
+
+impl Mempool {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            senders: HashMap::new(),
+            size: 0,
+        }
+    }
+
+    /// Number of transactions held
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Total bytes of every transaction held
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get(&self, hash: &Sha256Hash) -> Option<&Transaction> {
+        self.entries.get(hash).map(|entry| &entry.transaction)
+    }
+
+    /// Every transaction, grouped by sender in nonce order
+    pub fn transactions(&self) -> Vec<Transaction> {
+        let mut senders: Vec<&Sha256Hash> = self.senders.keys().collect();
+        senders.sort();
+        senders.iter()
+            .flat_map(|sender| self.senders[*sender].values())
+            .map(|hash| self.entries[hash].transaction.clone())
+            .collect()
+    }
+
+    /// Nonce the sender's next transaction should use
+    pub fn next_nonce(&self, sender: &Sha256Hash, ledger: &Ledger) -> u64 {
+        let pending = match self.senders.get(sender) {
+            Some(nonces) => nonces.len() as u64,
+            None => 0
+        };
+        ledger.nonce(sender) + pending
+    }
+
+    /// Amount plus fees of the sender's pending transactions, leaving out the one with nonce `except`
+    fn pending_spend(&self, sender: &Sha256Hash, except: u64) -> u64 {
+        match self.senders.get(sender) {
+            Some(nonces) => {
+                nonces.iter()
+                    .filter(|&(nonce, _)| *nonce != except)
+                    .map(|(_, hash)| &self.entries[hash].transaction)
+                    .fold(0 as u64, |total, transaction| {
+                        total.saturating_add(transaction.amount).saturating_add(transaction.fee)
+                    })
+            }
+            None => 0
+        }
+    }
+
+    /// Checks the signature and adds the transaction, returning its hash. A transaction reusing a pending nonce
+    /// replaces the pending one if it pays a higher fee.
+    pub fn add(&mut self, transaction: Transaction, ledger: &Ledger) -> Result<Sha256Hash, MempoolError> {
+        if !transaction.is_valid() {
+            return Err(MempoolError::InvalidSignature);
+        }
+        self.insert(transaction, ledger)
+    }
+
+    /// Adds a transaction whose signature has already been checked
+    fn insert(&mut self, transaction: Transaction, ledger: &Ledger) -> Result<Sha256Hash, MempoolError> {
+        let hash = transaction.hash();
+        if self.entries.contains_key(&hash) {
+            return Err(MempoolError::AlreadyKnown);
+        }
+        if transaction.amount == 0 {
+            return Err(MempoolError::ZeroAmount);
+        }
+
+        let sender = transaction.sender;
+        let first_nonce = ledger.nonce(&sender);
+        let next_nonce = self.next_nonce(&sender, ledger);
+        if transaction.nonce < first_nonce {
+            return Err(MempoolError::StaleNonce { expected: first_nonce, actual: transaction.nonce });
+        }
+        if transaction.nonce > next_nonce {
+            return Err(MempoolError::NonceGap { expected: next_nonce, actual: transaction.nonce });
+        }
+        let replaced = match self.senders.get(&sender).and_then(|nonces| nonces.get(&transaction.nonce)) {
+            Some(replaced) => {
+                let replaced_fee = self.entries[replaced].transaction.fee;
+                if transaction.fee <= replaced_fee {
+                    return Err(MempoolError::ReplacementFeeTooLow { fee: transaction.fee, replaced_fee });
+                }
+                Some(*replaced)
+            }
+            None => {
+                if next_nonce - first_nonce >= MAX_SENDER_TRANSACTIONS as u64 {
+                    return Err(MempoolError::TooManyFromSender);
+                }
+                None
+            }
+        };
+
+        let available = ledger.balance(&sender).saturating_sub(self.pending_spend(&sender, transaction.nonce));
+        let required = transaction.amount.saturating_add(transaction.fee);
+        if required > available {
+            return Err(MempoolError::InsufficientBalance { available, required });
+        }
+
+        match replaced {
+            Some(replaced) => self.remove(&replaced),
+            None => {}
+        }
+        let size = transaction.size();
+        self.senders.entry(sender).or_insert_with(BTreeMap::new).insert(transaction.nonce, hash);
+        self.entries.insert(hash, Entry { hash, transaction, size });
+        self.size += size;
+
+        while self.size > MAX_MEMPOOL_SIZE || self.entries.len() > MAX_MEMPOOL_TRANSACTIONS {
+            let lowest = self.lowest_fee_rate_tail();
+            self.remove(&lowest);
+            if lowest == hash {
+                return Err(MempoolError::FeeRateTooLow);
+            }
+        }
+        Ok(hash)
+    }
+
+    fn remove(&mut self, hash: &Sha256Hash) {
+        match self.entries.remove(hash) {
+            Some(entry) => {
+                self.size -= entry.size;
+                let sender = entry.transaction.sender;
+                let now_empty = match self.senders.get_mut(&sender) {
+                    Some(nonces) => {
+                        nonces.remove(&entry.transaction.nonce);
+                        nonces.is_empty()
+                    }
+                    None => false
+                };
+                if now_empty {
+                    self.senders.remove(&sender);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// The last transaction of whichever sender's last transaction pays the least per byte. Only ever evicting the
+    /// last transaction keeps every sender's nonces free of gaps.
+    fn lowest_fee_rate_tail(&self) -> Sha256Hash {
+        self.senders.values()
+            .filter_map(|nonces| nonces.values().next_back())
+            .map(|hash| &self.entries[hash])
+            .min_by(|a, b| a.cmp_fee_rate(b).then_with(|| b.hash.cmp(&a.hash)))
+            .map(|entry| entry.hash)
+            .expect("Evicting from an empty mempool")
+    }
+
+    /// Rechecks every transaction against the ledger once the main chain has changed, together with the
+    /// transactions from any blocks that left the main chain. Anything that has been mined, conflicts with a mined
+    /// transaction or can no longer be afforded is dropped. Returns how many transactions were dropped.
+    pub fn update(&mut self, ledger: &Ledger, returned: Vec<Transaction>) -> usize {
+        let mut transactions = returned;
+        transactions.extend(self.entries.drain().map(|(_, entry)| entry.transaction));
+        transactions.sort_by_key(|transaction| transaction.nonce);
+        self.senders.clear();
+        self.size = 0;
+
+        let mut dropped = 0;
+        for transaction in transactions {
+            match self.insert(transaction, ledger) {
+                Ok(_) | Err(MempoolError::AlreadyKnown) => {}
+                Err(_) => dropped += 1,
+            }
+        }
+        dropped
+    }
+
+    /// Picks the transactions for a block, taking whichever pays the highest fee per byte next while keeping each
+    /// sender's transactions in nonce order. A sender whose next transaction doesn't fit in what's left of
+    /// `max_size` is passed over so smaller transactions from others can still fill the block.
+    pub fn select(&self, max_size: usize) -> Vec<Transaction> {
+        let by_sender: Vec<Vec<&Entry>> = self.senders.values()
+            .map(|nonces| nonces.values().map(|hash| &self.entries[hash]).collect())
+            .collect();
+        let mut candidates: BinaryHeap<Candidate> = by_sender.iter()
+            .map(|entries| Candidate { entries: &entries[..], position: 0 })
+            .collect();
+
+        let mut selected = vec![];
+        let mut size = 0;
+        while let Some(candidate) = candidates.pop() {
+            let entry = candidate.entry();
+            if size + entry.size > max_size {
+                continue;
+            }
+            size += entry.size;
+            selected.push(entry.transaction.clone());
+            if candidate.position + 1 < candidate.entries.len() {
+                candidates.push(Candidate { entries: candidate.entries, position: candidate.position + 1 });
+            }
+        }
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {Block, Coinbase, MAX_BLOCK_SIZE};
+
+    fn address(n: usize) -> Sha256Hash {
+        let mut address = Sha256Hash::default();
+        address[0] = (n >> 8) as u8;
+        address[1] = n as u8;
+        address
+    }
+
+    /// The signature isn't checked by `insert`, so it's left blank
+    fn transaction(sender: Sha256Hash, nonce: u64, fee: u64) -> Transaction {
+        Transaction {
+            sender,
+            recipient: [0xff; 32],
+            amount: 1,
+            fee,
+            nonce,
+            public_key: [0; 32],
+            signature: vec![0; 64],
+        }
+    }
+
+    fn funded(senders: &[Sha256Hash]) -> Ledger {
+        let data: Vec<Box<BlockData>> = senders.iter()
+            .map(|sender| Coinbase::new(*sender, 1, 1000000))
+            .collect();
+        let mut ledger = Ledger::new();
+        ledger.apply_block(&Block::new(&data, Sha256Hash::default(), -1, 1, 1)).unwrap();
+        ledger
+    }
+
+    fn sender_nonces(transactions: &[Transaction]) -> Vec<(Sha256Hash, u64)> {
+        transactions.iter().map(|transaction| (transaction.sender, transaction.nonce)).collect()
+    }
+
+    #[test]
+    fn replacement_needs_a_higher_fee() {
+        let sender = address(1);
+        let ledger = funded(&[sender]);
+        let mut mempool = Mempool::new();
+        let original = mempool.insert(transaction(sender, 0, 10), &ledger).unwrap();
+
+        let mut same_fee = transaction(sender, 0, 10);
+        same_fee.amount = 2;
+        match mempool.insert(same_fee, &ledger) {
+            Err(MempoolError::ReplacementFeeTooLow { fee: 10, replaced_fee: 10 }) => {}
+            result => panic!("Expected the replacement to be rejected, got {:?}", result)
+        }
+
+        let replacement = transaction(sender, 0, 11);
+        let size = replacement.size();
+        let replacement = mempool.insert(replacement, &ledger).unwrap();
+        assert!(!mempool.entries.contains_key(&original));
+        assert!(mempool.entries.contains_key(&replacement));
+        assert_eq!((mempool.entries.len(), mempool.size), (1, size));
+        assert_eq!(mempool.next_nonce(&sender, &ledger), 1);
+
+        match mempool.insert(transaction(sender, 2, 10), &ledger) {
+            Err(MempoolError::NonceGap { expected: 1, actual: 2 }) => {}
+            result => panic!("Expected a nonce gap, got {:?}", result)
+        }
+    }
+
+    #[test]
+    fn eviction_keeps_nonces_gap_free() {
+        let sender_count = MAX_MEMPOOL_TRANSACTIONS / MAX_SENDER_TRANSACTIONS + 1;
+        let senders: Vec<Sha256Hash> = (0..sender_count).map(address).collect();
+        let ledger = funded(&senders);
+        let mut mempool = Mempool::new();
+        // The cheapest transaction of all is first in line for its sender, so it can't be evicted without leaving
+        // a gap
+        let cheapest = mempool.insert(transaction(senders[0], 0, 1), &ledger).unwrap();
+        for nonce in 1..MAX_SENDER_TRANSACTIONS as u64 {
+            mempool.insert(transaction(senders[0], nonce, 60), &ledger).unwrap();
+        }
+        for sender in senders[1..senders.len() - 1].iter() {
+            for nonce in 0..MAX_SENDER_TRANSACTIONS as u64 {
+                mempool.insert(transaction(*sender, nonce, 50), &ledger).unwrap();
+            }
+        }
+        assert_eq!(mempool.entries.len(), MAX_MEMPOOL_TRANSACTIONS);
+
+        let last_sender = senders[senders.len() - 1];
+        match mempool.insert(transaction(last_sender, 0, 40), &ledger) {
+            Err(MempoolError::FeeRateTooLow) => {}
+            result => panic!("Expected the mempool to be full, got {:?}", result)
+        }
+        mempool.insert(transaction(last_sender, 0, 100), &ledger).unwrap();
+        assert_eq!(mempool.entries.len(), MAX_MEMPOOL_TRANSACTIONS);
+        assert!(mempool.entries.contains_key(&cheapest));
+
+        for (sender, nonces) in mempool.senders.iter() {
+            let expected: Vec<u64> = (0..nonces.len() as u64).collect();
+            assert_eq!(nonces.keys().cloned().collect::<Vec<u64>>(), expected);
+            assert_eq!(mempool.next_nonce(sender, &ledger), nonces.len() as u64);
+        }
+        let evicted = senders[1..senders.len() - 1].iter()
+            .filter(|sender| mempool.senders[*sender].len() < MAX_SENDER_TRANSACTIONS)
+            .count();
+        assert_eq!(evicted, 1);
+    }
+
+    #[test]
+    fn selection_follows_fee_rate_in_nonce_order() {
+        let (a, b) = (address(1), address(2));
+        let ledger = funded(&[a, b]);
+        let mut mempool = Mempool::new();
+        mempool.insert(transaction(a, 0, 1), &ledger).unwrap();
+        mempool.insert(transaction(a, 1, 100), &ledger).unwrap();
+        mempool.insert(transaction(b, 0, 50), &ledger).unwrap();
+        let size = transaction(a, 0, 1).size();
+
+        assert_eq!(sender_nonces(&mempool.select(MAX_BLOCK_SIZE)), vec![(b, 0), (a, 0), (a, 1)]);
+        assert_eq!(sender_nonces(&mempool.select(2 * size)), vec![(b, 0), (a, 0)]);
+        assert_eq!(sender_nonces(&mempool.select(size - 1)), vec![]);
+    }
+}
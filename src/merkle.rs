@@ -0,0 +1,216 @@
+use crypto::sha2::Sha256;
+use crypto::digest::Digest;
+use hex;
+use {BlockInner, Sha256Hash};
+
+// Leaves and interior nodes are hashed with different prefixes so an interior node can never be
+// passed off as a leaf. An unpaired node is carried up to the next level unchanged.
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+pub fn leaf_hash(data: &[u8]) -> Sha256Hash {
+    let mut hasher = Sha256::new();
+    let mut hash = Sha256Hash::default();
+    hasher.input(&[LEAF_PREFIX]);
+    hasher.input(data);
+    hasher.result(&mut hash);
+    hash
+}
+
+pub fn node_hash(left: &Sha256Hash, right: &Sha256Hash) -> Sha256Hash {
+    let mut hasher = Sha256::new();
+    let mut hash = Sha256Hash::default();
+    hasher.input(&[NODE_PREFIX]);
+    hasher.input(left);
+    hasher.input(right);
+    hasher.result(&mut hash);
+    hash
+}
+
+fn next_level(level: &[Sha256Hash]) -> Vec<Sha256Hash> {
+    level.chunks(2)
+        .map(|pair| {
+            if pair.len() == 2 {
+                node_hash(&pair[0], &pair[1])
+            } else {
+                pair[0]
+            }
+        })
+        .collect()
+}
+
+/// Root of the Merkle tree over `leaves`, or all zeros if there are none
+pub fn merkle_root(leaves: &[Sha256Hash]) -> Sha256Hash {
+    if leaves.len() == 0 {
+        return Sha256Hash::default();
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// A sibling hash on the path from a leaf to the root
+#[derive(Debug, Clone)]
+pub struct ProofStep {
+    pub hash: Sha256Hash,
+    /// Whether the sibling is hashed to the left of the running hash, only informational as a verifier works it out
+    /// from the leaf index
+    pub left: bool,
+}
+
+graphql_object!(ProofStep: () |&self|{
+    field hash() -> String {
+        hex::encode_upper(&self.hash)
+    }
+
+    field left() -> bool {
+        self.left
+    }
+});
+
+#[derive(Debug, Clone)]
+pub struct MerkleProof {
+    pub block_hash: Sha256Hash,
+    pub merkle_root: Sha256Hash,
+    pub index: usize,
+    /// Number of leaves in the tree, needed to tell which levels the leaf is carried up unpaired
+    pub leaf_count: usize,
+    pub leaf: Sha256Hash,
+    pub siblings: Vec<ProofStep>,
+}
+
+graphql_object!(MerkleProof: () |&self|{
+    field block_hash() -> String {
+        hex::encode_upper(&self.block_hash)
+    }
+
+    field merkle_root() -> String {
+        hex::encode_upper(&self.merkle_root)
+    }
+
+    field index() -> i32 {
+        self.index as i32
+    }
+
+    field leaf_count() -> i32 {
+        self.leaf_count as i32
+    }
+
+    field leaf() -> String {
+        hex::encode_upper(&self.leaf)
+    }
+
+    field siblings() -> Vec<ProofStep> {
+        self.siblings.clone()
+    }
+});
+
+/// Sibling hashes needed to rebuild the root from the leaf at `index`, starting from the bottom of the tree
+pub fn merkle_proof(leaves: &[Sha256Hash], index: usize) -> Option<Vec<ProofStep>> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut steps = vec![];
+    let mut level = leaves.to_vec();
+    let mut index = index;
+    while level.len() > 1 {
+        if index % 2 == 1 {
+            steps.push(ProofStep { hash: level[index - 1], left: true });
+        } else if index + 1 < level.len() {
+            steps.push(ProofStep { hash: level[index + 1], left: false });
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(steps)
+}
+
+/// Rebuilds the root from the leaf at `index` of a tree with `leaf_count` leaves. Which side each sibling goes on
+/// comes from the index, and there must be exactly one sibling for every level where the leaf has a pair.
+fn verify_proof(root: &Sha256Hash, leaf: &Sha256Hash, index: usize, leaf_count: usize, siblings: &[ProofStep])
+                -> bool {
+    if index >= leaf_count {
+        return false;
+    }
+    let mut siblings = siblings.iter();
+    let mut hash = *leaf;
+    let mut index = index;
+    let mut width = leaf_count;
+    while width > 1 {
+        if index % 2 == 1 || index + 1 < width {
+            let sibling = match siblings.next() {
+                Some(step) => step.hash,
+                None => return false
+            };
+            hash = if index % 2 == 1 {
+                node_hash(&sibling, &hash)
+            } else {
+                node_hash(&hash, &sibling)
+            };
+        }
+        index /= 2;
+        width = (width + 1) / 2;
+    }
+    siblings.next().is_none() && hash == *root
+}
+
+/// Checks that `proof` shows `data` is committed to by `header`. A light client only needs the headers to check a
+/// `transactionProof` this way, the data in `header` isn't looked at.
+pub fn verify_data_proof(header: &BlockInner, data: &[u8], proof: &MerkleProof) -> bool {
+    let leaf = leaf_hash(data);
+    proof.block_hash == header.hash() && proof.merkle_root == header.merkle_root && proof.leaf == leaf &&
+        verify_proof(&header.merkle_root, &leaf, proof.index, proof.leaf_count, &proof.siblings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {BinaryData, Block, BlockData};
+
+    fn leaves(count: u8) -> Vec<Sha256Hash> {
+        (0..count).map(|i| leaf_hash(&[i])).collect()
+    }
+
+    #[test]
+    fn proofs_verify_against_the_root() {
+        for &count in [1, 2, 3, 5, 6].iter() {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let siblings = merkle_proof(&leaves, index).unwrap();
+                let other = leaf_hash(&[0xff]);
+                assert!(verify_proof(&root, leaf, index, leaves.len(), &siblings), "leaf {} of {}", index, count);
+                assert!(!verify_proof(&root, &other, index, leaves.len(), &siblings), "leaf {} of {}", index, count);
+                if siblings.len() > 0 {
+                    let short = &siblings[1..];
+                    assert!(!verify_proof(&root, leaf, index, leaves.len(), short), "leaf {} of {}", index, count);
+                }
+            }
+            assert!(merkle_proof(&leaves, leaves.len()).is_none());
+        }
+    }
+
+    #[test]
+    fn data_proof_is_checked_against_the_header() {
+        let data: Vec<Box<BlockData>> = (0..5).map(|i| BinaryData::new(&vec![i])).collect();
+        let block = Block::new(&data, Sha256Hash::default(), -1, 1, 1);
+        let other = Block::new(&data[..4].to_vec(), Sha256Hash::default(), -1, 1, 1);
+        let header = block.inner.read().unwrap();
+        let proof = block.data_proof(3).unwrap();
+        assert!(verify_data_proof(&header, &data[3].data(), &proof));
+        assert!(!verify_data_proof(&header, &data[4].data(), &proof));
+        assert!(!verify_data_proof(&other.inner.read().unwrap(), &data[3].data(), &proof));
+
+        // The sides come from the index, so flipping the flags changes nothing but a wrong index is caught
+        let mut flipped = proof.clone();
+        for step in flipped.siblings.iter_mut() {
+            step.left = !step.left;
+        }
+        assert!(verify_data_proof(&header, &data[3].data(), &flipped));
+        let mut moved = proof.clone();
+        moved.index = 2;
+        assert!(!verify_data_proof(&header, &data[3].data(), &moved));
+    }
+}
@@ -0,0 +1,74 @@
+use std::thread;
+use std::time;
+use std::sync::{Arc, RwLock};
+use p2p::Network;
+use {Blockchain, Sha256Hash};
+
+pub struct Miner {
+    exit_flag: Arc<RwLock<bool>>,
+    exited_flag: Arc<RwLock<bool>>,
+}
+
+impl Miner {
+    pub fn start(blockchain: Arc<RwLock<Blockchain>>, miner_address: Sha256Hash, network: Network, threads: usize)
+                 -> Self {
+        let exit_flag = Arc::new(RwLock::new(false));
+        let exited_flag = Arc::new(RwLock::new(false));
+
+        let exit_flag_2 = Arc::clone(&exit_flag);
+        let exited_flag_2 = Arc::clone(&exited_flag);
+        thread::spawn(move || {
+            loop {
+                if *exit_flag_2.read().unwrap() {
+                    break;
+                }
+
+                // Don't hold the chain lock while mining
+                let block = blockchain.read().unwrap().candidate_block(&miner_address);
+                let block = match block {
+                    Some(block) => block,
+                    None => {
+                        thread::sleep(time::Duration::from_secs(1));
+                        continue;
+                    }
+                };
+
+                // Start again on a new candidate as soon as another block takes over the tip
+                let parent_hash = block.inner.read().unwrap().prev_block_hash;
+                let exit_flag_3 = Arc::clone(&exit_flag_2);
+                let blockchain_2 = Arc::clone(&blockchain);
+                let abort = move || {
+                    *exit_flag_3.read().unwrap() ||
+                        blockchain_2.read().unwrap().tip().map(|tip| tip.hash()) != Some(parent_hash)
+                };
+
+                match block.mine_until(abort, threads) {
+                    Some(nonce) => {
+                        block.inner.write().unwrap().nonce = nonce;
+                        let result = blockchain.write().unwrap().add_block(block.clone());
+                        match result {
+                            Ok(_) => {
+                                network.announce_block(&block);
+                            }
+                            Err(e) => {
+                                println!("Discarding mined block: {}", e);
+                            }
+                        }
+                    }
+                    None => {}
+                }
+            }
+            *exited_flag_2.write().unwrap() = true;
+        });
+
+        Self {
+            exit_flag,
+            exited_flag,
+        }
+    }
+
+    pub fn stop(&self) {
+        *self.exit_flag.write().unwrap() = true;
+        while !*self.exited_flag.read().unwrap() {}
+    }
+}
@@ -0,0 +1,537 @@
+use std::thread;
+use std::time;
+use std::io;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, SocketAddr};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock, Mutex};
+use protobuf;
+use protobuf::Message;
+use hex;
+use proto::chain;
+use validation::{self, BlockError};
+use {Block, Blockchain, Transaction, Sha256Hash, block_to_pb, pb_to_block, block_header_to_pb,
+     pb_header_to_block, transaction_to_pb, pb_to_transaction, sha256hash_from_slice};
+
+pub const PROTOCOL_VERSION: u32 = 1;
+/// Largest envelope a peer may send, anything bigger drops the connection
+pub const MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+/// Most headers sent in reply to a single request
+pub const MAX_HEADERS: usize = 2000;
+/// Number of blocks requested before waiting for them to arrive
+pub const BLOCKS_IN_FLIGHT: usize = 16;
+/// Blocks whose parent we haven't seen yet are kept until it arrives, up to this many. Anything further behind is
+/// fetched through headers instead.
+const MAX_ORPHAN_BLOCKS: usize = 100;
+const RECONNECT_INTERVAL: u64 = 5;
+/// Seconds a send to a peer may block before the peer is dropped, so one stalled peer can't hold up a broadcast
+const WRITE_TIMEOUT: u64 = 30;
+
+/// Blocks being fetched from a peer whose main chain is ahead of ours
+#[derive(Default)]
+struct Downloads {
+    /// Hashes from the peer's headers that haven't been requested yet, oldest first
+    queued: VecDeque<Sha256Hash>,
+    /// Requested blocks that haven't arrived yet
+    in_flight: Vec<Sha256Hash>,
+    /// Headers have been requested but not received
+    awaiting_headers: bool,
+    /// The last headers were a full batch, so the peer probably has more after them
+    more_headers: bool,
+}
+
+struct Peer {
+    id: usize,
+    addr: SocketAddr,
+    magic: [u8; 4],
+    stream: Mutex<TcpStream>,
+    downloads: Mutex<Downloads>,
+}
+
+impl Peer {
+    fn send(&self, msg: &chain::Envelope) -> io::Result<()> {
+        let mut stream = self.stream.lock().unwrap();
+        let result = write_envelope(&mut *stream, &self.magic, msg);
+        if result.is_err() {
+            // The envelope may be half written, so nothing more can be sent. Shutting down also ends the reader.
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        result
+    }
+}
+
+/// Each envelope is sent as the network's magic bytes, a 4 byte big endian length, then the encoded message
+pub fn write_envelope(stream: &mut TcpStream, magic: &[u8; 4], msg: &chain::Envelope) -> io::Result<()> {
+    let bytes = match msg.write_to_bytes() {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(io::Error::new(io::ErrorKind::Other, e))
+    };
+    let len = bytes.len() as u32;
+    stream.write_all(magic)?;
+    stream.write_all(&[(len >> 24) as u8, (len >> 16) as u8, (len >> 8) as u8, len as u8])?;
+    stream.write_all(&bytes)?;
+    stream.flush()
+}
+
+pub fn read_envelope(stream: &mut TcpStream, magic: &[u8; 4]) -> io::Result<chain::Envelope> {
+    let mut magic_bytes = [0; 4];
+    stream.read_exact(&mut magic_bytes)?;
+    if magic_bytes != *magic {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "peer is on a different network"));
+    }
+    let mut len_bytes = [0; 4];
+    stream.read_exact(&mut len_bytes)?;
+    let len = len_bytes.iter().fold(0 as usize, |len, byte| (len << 8) | *byte as usize);
+    if len > MAX_MESSAGE_SIZE {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("message of {} bytes is too large", len)));
+    }
+    let mut bytes = vec![0; len];
+    stream.read_exact(&mut bytes)?;
+    match protobuf::parse_from_bytes::<chain::Envelope>(&bytes) {
+        Ok(msg) => Ok(msg),
+        Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+// The protobuf conversions assume hashes and keys are 32 bytes, so check that before trusting a peer's message
+fn well_formed_transaction(msg: &chain::Transaction) -> bool {
+    msg.get_from().len() == 32 && msg.get_to().len() == 32 && msg.get_public_key().len() == 32
+}
+
+pub fn well_formed_header(msg: &chain::BlockHeader) -> bool {
+    msg.get_prev_block_hash().len() == 32 && msg.get_merkle_root().len() == 32 && msg.get_difficulty() <= 255
+}
+
+pub fn well_formed_data(data: &chain::Block_Data) -> bool {
+    match data.get_field_type() {
+        chain::Block_DataType::BINARY_DATA => data.has_binaryData(),
+        chain::Block_DataType::TRANSACTION =>
+            data.has_transaction() && well_formed_transaction(data.get_transaction()),
+        chain::Block_DataType::COINBASE =>
+            data.has_coinbase() && data.get_coinbase().get_recipient().len() == 32,
+    }
+}
+
+pub fn well_formed_block(msg: &chain::Block) -> bool {
+    msg.get_prev_block_hash().len() == 32 && msg.get_merkle_root().len() == 32 && msg.get_difficulty() <= 255 &&
+        msg.get_data().iter().all(well_formed_data)
+}
+
+fn block_envelope(block: &Block) -> chain::Envelope {
+    let mut msg = chain::Envelope::new();
+    msg.set_field_type(chain::Envelope_MessageType::BLOCK);
+    msg.set_block(block_to_pb(block));
+    msg
+}
+
+fn transaction_envelope(transaction: &Transaction) -> chain::Envelope {
+    let mut msg = chain::Envelope::new();
+    msg.set_field_type(chain::Envelope_MessageType::TRANSACTION);
+    msg.set_transaction(transaction_to_pb(transaction));
+    msg
+}
+
+pub fn get_block_envelope(hash: &Sha256Hash) -> chain::Envelope {
+    let mut get_block = chain::GetBlock::new();
+    get_block.set_hash(hash.to_vec());
+    let mut msg = chain::Envelope::new();
+    msg.set_field_type(chain::Envelope_MessageType::GET_BLOCK);
+    msg.set_getBlock(get_block);
+    msg
+}
+
+pub fn get_headers_envelope(locator: &[Sha256Hash]) -> chain::Envelope {
+    let mut get_headers = chain::GetHeaders::new();
+    for hash in locator {
+        get_headers.mut_locator().push(hash.to_vec());
+    }
+    let mut msg = chain::Envelope::new();
+    msg.set_field_type(chain::Envelope_MessageType::GET_HEADERS);
+    msg.set_getHeaders(get_headers);
+    msg
+}
+
+/// Checks the headers form a chain off one of our blocks, returning the hashes of the blocks we don't have yet
+pub fn check_headers(chain: &Blockchain, headers: &[Block]) -> io::Result<Vec<Sha256Hash>> {
+    let mut hashes = vec![];
+    let mut prev_hash: Option<Sha256Hash> = None;
+    for header in headers {
+        let inner = header.inner.read().unwrap();
+        let hash = inner.hash();
+        match prev_hash {
+            Some(prev_hash) => {
+                if inner.prev_block_hash != prev_hash {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                              format!("header for block #{} does not follow the one before it",
+                                                      inner.id)));
+                }
+            }
+            None => {
+                if !chain.hash_index_map.contains_key(&inner.prev_block_hash) {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                              format!("header for block #{} does not connect to our chain", inner.id)));
+                }
+            }
+        }
+        if !validation::meets_target(&hash, inner.difficulty) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      format!("header for block #{} does not meet its difficulty target", inner.id)));
+        }
+        if !chain.hash_index_map.contains_key(&hash) {
+            hashes.push(hash);
+        }
+        prev_hash = Some(hash);
+    }
+    Ok(hashes)
+}
+
+pub fn handshake_envelope(chain: &Blockchain) -> chain::Envelope {
+    let tip = chain.main_chain.last().map(|index| &chain.blocks[*index]);
+    let mut handshake = chain::Handshake::new();
+    handshake.set_version(PROTOCOL_VERSION);
+    handshake.set_height(tip.map_or(0, |block| block.inner.read().unwrap().id));
+    handshake.set_tip_hash(tip.map_or(Sha256Hash::default(), |block| block.hash()).to_vec());
+    let mut msg = chain::Envelope::new();
+    msg.set_field_type(chain::Envelope_MessageType::HANDSHAKE);
+    msg.set_handshake(handshake);
+    msg
+}
+
+pub fn check_handshake(handshake: &chain::Handshake) -> io::Result<()> {
+    if handshake.get_version() != PROTOCOL_VERSION {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                  format!("unsupported protocol version {}", handshake.get_version())));
+    }
+    if handshake.get_tip_hash().len() != 32 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "malformed handshake"));
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct Network {
+    blockchain: Arc<RwLock<Blockchain>>,
+    magic: [u8; 4],
+    peers: Arc<RwLock<Vec<Arc<Peer>>>>,
+    next_peer_id: Arc<Mutex<usize>>,
+    /// Blocks waiting on a missing parent, keyed by the parent's hash
+    orphans: Arc<Mutex<HashMap<Sha256Hash, Vec<Block>>>>,
+}
+
+impl Network {
+    /// Listens for peers on `listen_addr` and keeps a connection open to each of `peer_addrs`
+    pub fn start(blockchain: Arc<RwLock<Blockchain>>, listen_addr: SocketAddr, peer_addrs: Vec<SocketAddr>)
+                 -> io::Result<Self> {
+        let magic = blockchain.read().unwrap().params.magic;
+        let network = Self {
+            blockchain,
+            magic,
+            peers: Arc::new(RwLock::new(vec![])),
+            next_peer_id: Arc::new(Mutex::new(0)),
+            orphans: Arc::new(Mutex::new(HashMap::new())),
+        };
+
+        let listener = TcpListener::bind(listen_addr)?;
+        println!("Listening for peers on {}", listen_addr);
+        let network_2 = network.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let network = network_2.clone();
+                        thread::spawn(move || network.run_peer(stream));
+                    }
+                    Err(e) => {
+                        println!("Failed to accept peer: {}", e);
+                    }
+                }
+            }
+        });
+
+        for addr in peer_addrs {
+            let network = network.clone();
+            thread::spawn(move || {
+                loop {
+                    match TcpStream::connect(addr) {
+                        Ok(stream) => network.run_peer(stream),
+                        Err(e) => {
+                            println!("Failed to connect to peer {}: {}", addr, e);
+                        }
+                    }
+                    thread::sleep(time::Duration::from_secs(RECONNECT_INTERVAL));
+                }
+            });
+        }
+
+        Ok(network)
+    }
+
+    pub fn announce_block(&self, block: &Block) {
+        self.broadcast(&block_envelope(block), None);
+    }
+
+    pub fn announce_transaction(&self, transaction: &Transaction) {
+        self.broadcast(&transaction_envelope(transaction), None);
+    }
+
+    fn broadcast(&self, msg: &chain::Envelope, except: Option<usize>) {
+        let peers = self.peers.read().unwrap().clone();
+        for peer in peers.iter().filter(|peer| Some(peer.id) != except) {
+            match peer.send(msg) {
+                Ok(_) => {}
+                Err(e) => {
+                    println!("Failed to send to peer {}: {}", peer.addr, e);
+                }
+            }
+        }
+    }
+
+    /// Talks to a connected peer until it disconnects or misbehaves
+    fn run_peer(&self, stream: TcpStream) {
+        let addr = match stream.peer_addr() {
+            Ok(addr) => addr,
+            Err(_) => return
+        };
+        match stream.set_write_timeout(Some(time::Duration::from_secs(WRITE_TIMEOUT))) {
+            Ok(_) => {}
+            Err(e) => {
+                println!("Failed to set up peer {}: {}", addr, e);
+                return;
+            }
+        }
+        let mut reader = match stream.try_clone() {
+            Ok(reader) => reader,
+            Err(e) => {
+                println!("Failed to set up peer {}: {}", addr, e);
+                return;
+            }
+        };
+        let peer = {
+            let mut next_peer_id = self.next_peer_id.lock().unwrap();
+            *next_peer_id += 1;
+            Arc::new(Peer {
+                id: *next_peer_id,
+                addr,
+                magic: self.magic,
+                stream: Mutex::new(stream),
+                downloads: Mutex::new(Downloads::default()),
+            })
+        };
+
+        println!("Connected to peer {}", addr);
+        let handshake = handshake_envelope(&self.blockchain.read().unwrap());
+        match peer.send(&handshake) {
+            Ok(_) => {
+                self.peers.write().unwrap().push(Arc::clone(&peer));
+                match self.read_messages(&peer, &mut reader) {
+                    Ok(_) => {}
+                    Err(e) => {
+                        println!("Dropping peer {}: {}", addr, e);
+                    }
+                }
+                self.peers.write().unwrap().retain(|other| other.id != peer.id);
+            }
+            Err(e) => {
+                println!("Failed to send handshake to peer {}: {}", addr, e);
+            }
+        }
+        println!("Disconnected from peer {}", addr);
+    }
+
+    fn read_messages(&self, peer: &Peer, reader: &mut TcpStream) -> io::Result<()> {
+        let msg = read_envelope(reader, &self.magic)?;
+        if msg.get_field_type() != chain::Envelope_MessageType::HANDSHAKE || !msg.has_handshake() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "expected a handshake"));
+        }
+        self.handle_handshake(peer, msg.get_handshake())?;
+
+        loop {
+            let msg = read_envelope(reader, &self.magic)?;
+            match msg.get_field_type() {
+                chain::Envelope_MessageType::HANDSHAKE => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "unexpected handshake"));
+                }
+                chain::Envelope_MessageType::BLOCK if msg.has_block() && well_formed_block(msg.get_block()) => {
+                    self.handle_block(peer, pb_to_block(msg.get_block()))?;
+                }
+                chain::Envelope_MessageType::TRANSACTION
+                if msg.has_transaction() && well_formed_transaction(msg.get_transaction()) => {
+                    self.handle_transaction(peer, pb_to_transaction(msg.get_transaction()));
+                }
+                chain::Envelope_MessageType::GET_BLOCK
+                if msg.has_getBlock() && msg.get_getBlock().get_hash().len() == 32 => {
+                    self.handle_get_block(peer, &sha256hash_from_slice(msg.get_getBlock().get_hash()))?;
+                }
+                chain::Envelope_MessageType::GET_HEADERS
+                if msg.has_getHeaders() && msg.get_getHeaders().get_locator().iter().all(|hash| hash.len() == 32) => {
+                    self.handle_get_headers(peer, msg.get_getHeaders())?;
+                }
+                chain::Envelope_MessageType::HEADERS
+                if msg.has_headers() && msg.get_headers().get_headers().len() <= MAX_HEADERS &&
+                    msg.get_headers().get_headers().iter().all(well_formed_header) => {
+                    let headers: Vec<Block> = msg.get_headers().get_headers().iter().map(pb_header_to_block).collect();
+                    self.handle_headers(peer, &headers)?;
+                }
+                _ => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "malformed message"));
+                }
+            }
+        }
+    }
+
+    fn handle_handshake(&self, peer: &Peer, handshake: &chain::Handshake) -> io::Result<()> {
+        check_handshake(handshake)?;
+        let tip_hash = sha256hash_from_slice(handshake.get_tip_hash());
+        let known = self.blockchain.read().unwrap().hash_index_map.contains_key(&tip_hash);
+        if !known {
+            println!("Peer {} is at block #{}, requesting the blocks we're missing", peer.addr,
+                     handshake.get_height());
+            self.request_headers(peer)?;
+        }
+        Ok(())
+    }
+
+    /// Asks the peer for the headers after our main chain, unless we're already downloading from it
+    fn request_headers(&self, peer: &Peer) -> io::Result<()> {
+        {
+            let mut downloads = peer.downloads.lock().unwrap();
+            if downloads.awaiting_headers || !downloads.queued.is_empty() || !downloads.in_flight.is_empty() {
+                return Ok(());
+            }
+            downloads.awaiting_headers = true;
+        }
+        let locator = self.blockchain.read().unwrap().locator();
+        peer.send(&get_headers_envelope(&locator))
+    }
+
+    fn handle_headers(&self, peer: &Peer, headers: &[Block]) -> io::Result<()> {
+        if !peer.downloads.lock().unwrap().awaiting_headers {
+            return Ok(());
+        }
+        let hashes = check_headers(&self.blockchain.read().unwrap(), headers)?;
+        {
+            let mut downloads = peer.downloads.lock().unwrap();
+            downloads.awaiting_headers = false;
+            downloads.more_headers = headers.len() == MAX_HEADERS;
+            downloads.queued.extend(hashes);
+        }
+        self.continue_download(peer)
+    }
+
+    /// Keeps up to `BLOCKS_IN_FLIGHT` of the queued blocks requested, asking for more headers once they've all
+    /// arrived if the peer has more
+    fn continue_download(&self, peer: &Peer) -> io::Result<()> {
+        let (requests, more_headers) = {
+            let mut downloads = peer.downloads.lock().unwrap();
+            let mut requests = vec![];
+            while downloads.in_flight.len() < BLOCKS_IN_FLIGHT {
+                match downloads.queued.pop_front() {
+                    Some(hash) => {
+                        downloads.in_flight.push(hash);
+                        requests.push(hash);
+                    }
+                    None => break
+                }
+            }
+            let more_headers = downloads.in_flight.is_empty() && downloads.more_headers;
+            if more_headers {
+                downloads.more_headers = false;
+            }
+            (requests, more_headers)
+        };
+        for hash in requests.iter() {
+            peer.send(&get_block_envelope(hash))?;
+        }
+        if more_headers {
+            self.request_headers(peer)?;
+        }
+        Ok(())
+    }
+
+    fn handle_block(&self, peer: &Peer, block: Block) -> io::Result<()> {
+        let hash = block.hash();
+        let requested = {
+            let mut downloads = peer.downloads.lock().unwrap();
+            match downloads.in_flight.iter().position(|in_flight| *in_flight == hash) {
+                Some(position) => {
+                    downloads.in_flight.remove(position);
+                    true
+                }
+                None => false
+            }
+        };
+        let result = self.blockchain.write().unwrap().add_block(block.clone());
+        match result {
+            Ok(_) => {
+                // Blocks we asked for while catching up are old news to our other peers
+                if !requested {
+                    self.broadcast(&block_envelope(&block), Some(peer.id));
+                }
+                // Anything that was waiting on this block can now be added too
+                let children = self.orphans.lock().unwrap().remove(&hash);
+                for child in children.unwrap_or(vec![]) {
+                    self.handle_block(peer, child)?;
+                }
+            }
+            Err(BlockError::AlreadyKnown) => {}
+            Err(BlockError::UnknownParent(parent_hash)) => {
+                {
+                    let mut orphans = self.orphans.lock().unwrap();
+                    let count: usize = orphans.values().map(|blocks| blocks.len()).sum();
+                    if count >= MAX_ORPHAN_BLOCKS {
+                        orphans.clear();
+                    }
+                    orphans.entry(parent_hash).or_insert(vec![]).push(block);
+                }
+                self.request_headers(peer)?;
+            }
+            Err(e) => {
+                println!("Rejected block {} from peer {}: {}", hex::encode_upper(&hash), peer.addr, e);
+                // The rest of the download builds on this block
+                if requested {
+                    *peer.downloads.lock().unwrap() = Downloads::default();
+                }
+            }
+        }
+        if requested {
+            self.continue_download(peer)?;
+        }
+        Ok(())
+    }
+
+    fn handle_transaction(&self, peer: &Peer, transaction: Transaction) {
+        let result = self.blockchain.write().unwrap().add_transaction(transaction.clone());
+        match result {
+            Ok(_) => self.broadcast(&transaction_envelope(&transaction), Some(peer.id)),
+            Err(_) => {}
+        }
+    }
+
+    fn handle_get_headers(&self, peer: &Peer, get_headers: &chain::GetHeaders) -> io::Result<()> {
+        let locator: Vec<Sha256Hash> = get_headers.get_locator().iter()
+            .map(|hash| sha256hash_from_slice(hash))
+            .collect();
+        let mut headers = chain::Headers::new();
+        {
+            let chain = self.blockchain.read().unwrap();
+            for block in chain.blocks_after_locator(&locator, MAX_HEADERS) {
+                headers.mut_headers().push(block_header_to_pb(block));
+            }
+        }
+        let mut msg = chain::Envelope::new();
+        msg.set_field_type(chain::Envelope_MessageType::HEADERS);
+        msg.set_headers(headers);
+        peer.send(&msg)
+    }
+
+    fn handle_get_block(&self, peer: &Peer, hash: &Sha256Hash) -> io::Result<()> {
+        let block = {
+            let chain = self.blockchain.read().unwrap();
+            chain.hash_index_map.get(hash).map(|index| chain.blocks[*index as usize].clone())
+        };
+        match block {
+            Some(block) => peer.send(&block_envelope(&block)),
+            None => Ok(())
+        }
+    }
+}
@@ -0,0 +1,93 @@
+/// Everything that differs between networks. Nodes only talk to peers using the same magic bytes, and only accept
+/// chains built on the same genesis block.
+#[derive(Debug)]
+pub struct NetworkParams {
+    pub name: &'static str,
+    /// Sent at the start of every peer to peer message
+    pub magic: [u8; 4],
+    pub genesis_timestamp: i64,
+    pub genesis_nonce: u64,
+    pub genesis_data: &'static [u8],
+    pub genesis_difficulty: u8,
+    /// Coinbase value of the first blocks, not counting fees
+    pub initial_block_reward: u64,
+    /// Number of blocks between each halving of the block reward
+    pub halving_interval: u64,
+    /// Whether the difficulty is adjusted to keep to `TARGET_BLOCK_TIME`, otherwise it stays at the genesis
+    /// difficulty
+    pub retarget: bool,
+    /// Blocks are only mined when asked for over GraphQL, instead of continuously in the background
+    pub on_demand_mining: bool,
+    pub p2p_port: u16,
+    pub graphql_port: u16,
+    pub data_dir: &'static str,
+}
+
+pub static MAINNET: NetworkParams = NetworkParams {
+    name: "mainnet",
+    magic: [0xb0, 0x6c, 0xa7, 0x01],
+    genesis_timestamp: 1538352000,
+    genesis_nonce: 302,
+    genesis_data: b"Genesis block",
+    genesis_difficulty: 0xa,
+    initial_block_reward: 5000,
+    halving_interval: 210000,
+    retarget: true,
+    on_demand_mining: false,
+    p2p_port: 3001,
+    graphql_port: 3000,
+    data_dir: "./blocks",
+};
+
+pub static TESTNET: NetworkParams = NetworkParams {
+    name: "testnet",
+    magic: [0xb0, 0x6c, 0xa7, 0x02],
+    genesis_timestamp: 1538352000,
+    genesis_nonce: 321,
+    genesis_data: b"Testnet genesis block",
+    genesis_difficulty: 0x8,
+    initial_block_reward: 5000,
+    halving_interval: 210000,
+    retarget: true,
+    on_demand_mining: false,
+    p2p_port: 13001,
+    graphql_port: 13000,
+    data_dir: "./testnet/blocks",
+};
+
+pub static REGTEST: NetworkParams = NetworkParams {
+    name: "regtest",
+    magic: [0xb0, 0x6c, 0xa7, 0x03],
+    genesis_timestamp: 1538352000,
+    genesis_nonce: 0,
+    genesis_data: b"Regtest genesis block",
+    genesis_difficulty: 1,
+    initial_block_reward: 5000,
+    halving_interval: 150,
+    retarget: false,
+    on_demand_mining: true,
+    p2p_port: 23001,
+    graphql_port: 23000,
+    data_dir: "./regtest/blocks",
+};
+
+impl NetworkParams {
+    /// Coinbase value of the block at `height`, not counting fees
+    pub fn block_reward(&self, height: u64) -> u64 {
+        let halvings = height / self.halving_interval;
+        if halvings >= 64 {
+            0
+        } else {
+            self.initial_block_reward >> halvings
+        }
+    }
+}
+
+pub fn from_name(name: &str) -> Option<&'static NetworkParams> {
+    match name {
+        "mainnet" => Some(&MAINNET),
+        "testnet" => Some(&TESTNET),
+        "regtest" => Some(&REGTEST),
+        _ => None
+    }
+}
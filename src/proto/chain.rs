@@ -27,6 +27,10 @@ pub struct Transaction {
     from: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     to: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     amount: ::std::option::Option<u64>,
+    public_key: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    signature: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    fee: ::std::option::Option<u64>,
+    nonce: ::std::option::Option<u64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -127,6 +131,116 @@ impl Transaction {
     pub fn get_amount(&self) -> u64 {
         self.amount.unwrap_or(0)
     }
+
+    // required bytes public_key = 4;
+
+    pub fn clear_public_key(&mut self) {
+        self.public_key.clear();
+    }
+
+    pub fn has_public_key(&self) -> bool {
+        self.public_key.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_public_key(&mut self, v: ::std::vec::Vec<u8>) {
+        self.public_key = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_public_key(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.public_key.is_none() {
+            self.public_key.set_default();
+        }
+        self.public_key.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_public_key(&mut self) -> ::std::vec::Vec<u8> {
+        self.public_key.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_public_key(&self) -> &[u8] {
+        match self.public_key.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+
+    // required bytes signature = 5;
+
+    pub fn clear_signature(&mut self) {
+        self.signature.clear();
+    }
+
+    pub fn has_signature(&self) -> bool {
+        self.signature.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_signature(&mut self, v: ::std::vec::Vec<u8>) {
+        self.signature = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_signature(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.signature.is_none() {
+            self.signature.set_default();
+        }
+        self.signature.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_signature(&mut self) -> ::std::vec::Vec<u8> {
+        self.signature.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_signature(&self) -> &[u8] {
+        match self.signature.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+
+    // required uint64 fee = 6;
+
+    pub fn clear_fee(&mut self) {
+        self.fee = ::std::option::Option::None;
+    }
+
+    pub fn has_fee(&self) -> bool {
+        self.fee.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fee(&mut self, v: u64) {
+        self.fee = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_fee(&self) -> u64 {
+        self.fee.unwrap_or(0)
+    }
+
+    // required uint64 nonce = 7;
+
+    pub fn clear_nonce(&mut self) {
+        self.nonce = ::std::option::Option::None;
+    }
+
+    pub fn has_nonce(&self) -> bool {
+        self.nonce.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nonce(&mut self, v: u64) {
+        self.nonce = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_nonce(&self) -> u64 {
+        self.nonce.unwrap_or(0)
+    }
 }
 
 impl ::protobuf::Message for Transaction {
@@ -140,6 +254,18 @@ impl ::protobuf::Message for Transaction {
         if self.amount.is_none() {
             return false;
         }
+        if self.public_key.is_none() {
+            return false;
+        }
+        if self.signature.is_none() {
+            return false;
+        }
+        if self.fee.is_none() {
+            return false;
+        }
+        if self.nonce.is_none() {
+            return false;
+        }
         true
     }
 
@@ -160,6 +286,26 @@ impl ::protobuf::Message for Transaction {
                     let tmp = is.read_uint64()?;
                     self.amount = ::std::option::Option::Some(tmp);
                 },
+                4 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.public_key)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.signature)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.fee = ::std::option::Option::Some(tmp);
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.nonce = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -181,6 +327,18 @@ impl ::protobuf::Message for Transaction {
         if let Some(v) = self.amount {
             my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
         }
+        if let Some(ref v) = self.public_key.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(4, &v);
+        }
+        if let Some(ref v) = self.signature.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(5, &v);
+        }
+        if let Some(v) = self.fee {
+            my_size += ::protobuf::rt::value_size(6, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.nonce {
+            my_size += ::protobuf::rt::value_size(7, v, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -196,6 +354,18 @@ impl ::protobuf::Message for Transaction {
         if let Some(v) = self.amount {
             os.write_uint64(3, v)?;
         }
+        if let Some(ref v) = self.public_key.as_ref() {
+            os.write_bytes(4, &v)?;
+        }
+        if let Some(ref v) = self.signature.as_ref() {
+            os.write_bytes(5, &v)?;
+        }
+        if let Some(v) = self.fee {
+            os.write_uint64(6, v)?;
+        }
+        if let Some(v) = self.nonce {
+            os.write_uint64(7, v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -253,6 +423,26 @@ impl ::protobuf::Message for Transaction {
                     |m: &Transaction| { &m.amount },
                     |m: &mut Transaction| { &mut m.amount },
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "public_key",
+                    |m: &Transaction| { &m.public_key },
+                    |m: &mut Transaction| { &mut m.public_key },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "signature",
+                    |m: &Transaction| { &m.signature },
+                    |m: &mut Transaction| { &mut m.signature },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "fee",
+                    |m: &Transaction| { &m.fee },
+                    |m: &mut Transaction| { &mut m.fee },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "nonce",
+                    |m: &Transaction| { &m.nonce },
+                    |m: &mut Transaction| { &mut m.nonce },
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Transaction>(
                     "Transaction",
                     fields,
@@ -278,6 +468,10 @@ impl ::protobuf::Clear for Transaction {
         self.clear_from();
         self.clear_to();
         self.clear_amount();
+        self.clear_public_key();
+        self.clear_signature();
+        self.clear_fee();
+        self.clear_nonce();
         self.unknown_fields.clear();
     }
 }
@@ -471,161 +665,107 @@ impl ::protobuf::reflect::ProtobufValue for BinaryData {
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct Block {
+pub struct Coinbase {
     // message fields
-    id: ::std::option::Option<u64>,
-    timestamp: ::std::option::Option<i64>,
-    nonce: ::std::option::Option<u64>,
-    prev_block_hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
-    data: ::protobuf::RepeatedField<Block_Data>,
+    recipient: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    amount: ::std::option::Option<u64>,
+    height: ::std::option::Option<u64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
 }
 
-impl Block {
-    pub fn new() -> Block {
+impl Coinbase {
+    pub fn new() -> Coinbase {
         ::std::default::Default::default()
     }
 
-    // required uint64 id = 1;
-
-    pub fn clear_id(&mut self) {
-        self.id = ::std::option::Option::None;
-    }
-
-    pub fn has_id(&self) -> bool {
-        self.id.is_some()
-    }
-
-    // Param is passed by value, moved
-    pub fn set_id(&mut self, v: u64) {
-        self.id = ::std::option::Option::Some(v);
-    }
-
-    pub fn get_id(&self) -> u64 {
-        self.id.unwrap_or(0)
-    }
-
-    // required int64 timestamp = 2;
+    // required bytes recipient = 1;
 
-    pub fn clear_timestamp(&mut self) {
-        self.timestamp = ::std::option::Option::None;
+    pub fn clear_recipient(&mut self) {
+        self.recipient.clear();
     }
 
-    pub fn has_timestamp(&self) -> bool {
-        self.timestamp.is_some()
+    pub fn has_recipient(&self) -> bool {
+        self.recipient.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_timestamp(&mut self, v: i64) {
-        self.timestamp = ::std::option::Option::Some(v);
-    }
-
-    pub fn get_timestamp(&self) -> i64 {
-        self.timestamp.unwrap_or(0)
-    }
-
-    // required uint64 nonce = 3;
-
-    pub fn clear_nonce(&mut self) {
-        self.nonce = ::std::option::Option::None;
+    pub fn set_recipient(&mut self, v: ::std::vec::Vec<u8>) {
+        self.recipient = ::protobuf::SingularField::some(v);
     }
 
-    pub fn has_nonce(&self) -> bool {
-        self.nonce.is_some()
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_recipient(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.recipient.is_none() {
+            self.recipient.set_default();
+        }
+        self.recipient.as_mut().unwrap()
     }
 
-    // Param is passed by value, moved
-    pub fn set_nonce(&mut self, v: u64) {
-        self.nonce = ::std::option::Option::Some(v);
+    // Take field
+    pub fn take_recipient(&mut self) -> ::std::vec::Vec<u8> {
+        self.recipient.take().unwrap_or_else(|| ::std::vec::Vec::new())
     }
 
-    pub fn get_nonce(&self) -> u64 {
-        self.nonce.unwrap_or(0)
+    pub fn get_recipient(&self) -> &[u8] {
+        match self.recipient.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
     }
 
-    // required bytes prev_block_hash = 4;
+    // required uint64 amount = 2;
 
-    pub fn clear_prev_block_hash(&mut self) {
-        self.prev_block_hash.clear();
+    pub fn clear_amount(&mut self) {
+        self.amount = ::std::option::Option::None;
     }
 
-    pub fn has_prev_block_hash(&self) -> bool {
-        self.prev_block_hash.is_some()
+    pub fn has_amount(&self) -> bool {
+        self.amount.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_prev_block_hash(&mut self, v: ::std::vec::Vec<u8>) {
-        self.prev_block_hash = ::protobuf::SingularField::some(v);
+    pub fn set_amount(&mut self, v: u64) {
+        self.amount = ::std::option::Option::Some(v);
     }
 
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_prev_block_hash(&mut self) -> &mut ::std::vec::Vec<u8> {
-        if self.prev_block_hash.is_none() {
-            self.prev_block_hash.set_default();
-        }
-        self.prev_block_hash.as_mut().unwrap()
+    pub fn get_amount(&self) -> u64 {
+        self.amount.unwrap_or(0)
     }
 
-    // Take field
-    pub fn take_prev_block_hash(&mut self) -> ::std::vec::Vec<u8> {
-        self.prev_block_hash.take().unwrap_or_else(|| ::std::vec::Vec::new())
-    }
+    // required uint64 height = 3;
 
-    pub fn get_prev_block_hash(&self) -> &[u8] {
-        match self.prev_block_hash.as_ref() {
-            Some(v) => &v,
-            None => &[],
-        }
+    pub fn clear_height(&mut self) {
+        self.height = ::std::option::Option::None;
     }
 
-    // repeated .Block.Data data = 5;
-
-    pub fn clear_data(&mut self) {
-        self.data.clear();
+    pub fn has_height(&self) -> bool {
+        self.height.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_data(&mut self, v: ::protobuf::RepeatedField<Block_Data>) {
-        self.data = v;
-    }
-
-    // Mutable pointer to the field.
-    pub fn mut_data(&mut self) -> &mut ::protobuf::RepeatedField<Block_Data> {
-        &mut self.data
-    }
-
-    // Take field
-    pub fn take_data(&mut self) -> ::protobuf::RepeatedField<Block_Data> {
-        ::std::mem::replace(&mut self.data, ::protobuf::RepeatedField::new())
+    pub fn set_height(&mut self, v: u64) {
+        self.height = ::std::option::Option::Some(v);
     }
 
-    pub fn get_data(&self) -> &[Block_Data] {
-        &self.data
+    pub fn get_height(&self) -> u64 {
+        self.height.unwrap_or(0)
     }
 }
 
-impl ::protobuf::Message for Block {
+impl ::protobuf::Message for Coinbase {
     fn is_initialized(&self) -> bool {
-        if self.id.is_none() {
-            return false;
-        }
-        if self.timestamp.is_none() {
+        if self.recipient.is_none() {
             return false;
         }
-        if self.nonce.is_none() {
+        if self.amount.is_none() {
             return false;
         }
-        if self.prev_block_hash.is_none() {
+        if self.height.is_none() {
             return false;
         }
-        for v in &self.data {
-            if !v.is_initialized() {
-                return false;
-            }
-        };
         true
     }
 
@@ -634,31 +774,21 @@ impl ::protobuf::Message for Block {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_uint64()?;
-                    self.id = ::std::option::Option::Some(tmp);
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.recipient)?;
                 },
                 2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    let tmp = is.read_int64()?;
-                    self.timestamp = ::std::option::Option::Some(tmp);
+                    let tmp = is.read_uint64()?;
+                    self.amount = ::std::option::Option::Some(tmp);
                 },
                 3 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_uint64()?;
-                    self.nonce = ::std::option::Option::Some(tmp);
-                },
-                4 => {
-                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.prev_block_hash)?;
-                },
-                5 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.data)?;
+                    self.height = ::std::option::Option::Some(tmp);
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -672,19 +802,2025 @@ impl ::protobuf::Message for Block {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if let Some(v) = self.id {
-            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        if let Some(ref v) = self.recipient.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(1, &v);
+        }
+        if let Some(v) = self.amount {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.height {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.recipient.as_ref() {
+            os.write_bytes(1, &v)?;
+        }
+        if let Some(v) = self.amount {
+            os.write_uint64(2, v)?;
+        }
+        if let Some(v) = self.height {
+            os.write_uint64(3, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Coinbase {
+        Coinbase::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "recipient",
+                    |m: &Coinbase| { &m.recipient },
+                    |m: &mut Coinbase| { &mut m.recipient },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "amount",
+                    |m: &Coinbase| { &m.amount },
+                    |m: &mut Coinbase| { &mut m.amount },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "height",
+                    |m: &Coinbase| { &m.height },
+                    |m: &mut Coinbase| { &mut m.height },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Coinbase>(
+                    "Coinbase",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Coinbase {
+        static mut instance: ::protobuf::lazy::Lazy<Coinbase> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Coinbase,
+        };
+        unsafe {
+            instance.get(Coinbase::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Coinbase {
+    fn clear(&mut self) {
+        self.clear_recipient();
+        self.clear_amount();
+        self.clear_height();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Coinbase {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Coinbase {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Block {
+    // message fields
+    id: ::std::option::Option<u64>,
+    timestamp: ::std::option::Option<i64>,
+    nonce: ::std::option::Option<u64>,
+    prev_block_hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    data: ::protobuf::RepeatedField<Block_Data>,
+    difficulty: ::std::option::Option<u32>,
+    merkle_root: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+impl Block {
+    pub fn new() -> Block {
+        ::std::default::Default::default()
+    }
+
+    // required uint64 id = 1;
+
+    pub fn clear_id(&mut self) {
+        self.id = ::std::option::Option::None;
+    }
+
+    pub fn has_id(&self) -> bool {
+        self.id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_id(&mut self, v: u64) {
+        self.id = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_id(&self) -> u64 {
+        self.id.unwrap_or(0)
+    }
+
+    // required int64 timestamp = 2;
+
+    pub fn clear_timestamp(&mut self) {
+        self.timestamp = ::std::option::Option::None;
+    }
+
+    pub fn has_timestamp(&self) -> bool {
+        self.timestamp.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timestamp(&mut self, v: i64) {
+        self.timestamp = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_timestamp(&self) -> i64 {
+        self.timestamp.unwrap_or(0)
+    }
+
+    // required uint64 nonce = 3;
+
+    pub fn clear_nonce(&mut self) {
+        self.nonce = ::std::option::Option::None;
+    }
+
+    pub fn has_nonce(&self) -> bool {
+        self.nonce.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nonce(&mut self, v: u64) {
+        self.nonce = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_nonce(&self) -> u64 {
+        self.nonce.unwrap_or(0)
+    }
+
+    // required bytes prev_block_hash = 4;
+
+    pub fn clear_prev_block_hash(&mut self) {
+        self.prev_block_hash.clear();
+    }
+
+    pub fn has_prev_block_hash(&self) -> bool {
+        self.prev_block_hash.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_prev_block_hash(&mut self, v: ::std::vec::Vec<u8>) {
+        self.prev_block_hash = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_prev_block_hash(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.prev_block_hash.is_none() {
+            self.prev_block_hash.set_default();
+        }
+        self.prev_block_hash.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_prev_block_hash(&mut self) -> ::std::vec::Vec<u8> {
+        self.prev_block_hash.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_prev_block_hash(&self) -> &[u8] {
+        match self.prev_block_hash.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+
+    // repeated .Block.Data data = 5;
+
+    pub fn clear_data(&mut self) {
+        self.data.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_data(&mut self, v: ::protobuf::RepeatedField<Block_Data>) {
+        self.data = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_data(&mut self) -> &mut ::protobuf::RepeatedField<Block_Data> {
+        &mut self.data
+    }
+
+    // Take field
+    pub fn take_data(&mut self) -> ::protobuf::RepeatedField<Block_Data> {
+        ::std::mem::replace(&mut self.data, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_data(&self) -> &[Block_Data] {
+        &self.data
+    }
+
+    // required uint32 difficulty = 6;
+
+    pub fn clear_difficulty(&mut self) {
+        self.difficulty = ::std::option::Option::None;
+    }
+
+    pub fn has_difficulty(&self) -> bool {
+        self.difficulty.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_difficulty(&mut self, v: u32) {
+        self.difficulty = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_difficulty(&self) -> u32 {
+        self.difficulty.unwrap_or(0)
+    }
+
+    // required bytes merkle_root = 7;
+
+    pub fn clear_merkle_root(&mut self) {
+        self.merkle_root.clear();
+    }
+
+    pub fn has_merkle_root(&self) -> bool {
+        self.merkle_root.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_merkle_root(&mut self, v: ::std::vec::Vec<u8>) {
+        self.merkle_root = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_merkle_root(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.merkle_root.is_none() {
+            self.merkle_root.set_default();
+        }
+        self.merkle_root.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_merkle_root(&mut self) -> ::std::vec::Vec<u8> {
+        self.merkle_root.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_merkle_root(&self) -> &[u8] {
+        match self.merkle_root.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+}
+
+impl ::protobuf::Message for Block {
+    fn is_initialized(&self) -> bool {
+        if self.id.is_none() {
+            return false;
+        }
+        if self.timestamp.is_none() {
+            return false;
+        }
+        if self.nonce.is_none() {
+            return false;
+        }
+        if self.prev_block_hash.is_none() {
+            return false;
+        }
+        if self.difficulty.is_none() {
+            return false;
+        }
+        if self.merkle_root.is_none() {
+            return false;
+        }
+        for v in &self.data {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.id = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.timestamp = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.nonce = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.prev_block_hash)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.data)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.difficulty = ::std::option::Option::Some(tmp);
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.merkle_root)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.id {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.timestamp {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.nonce {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.prev_block_hash.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(4, &v);
+        }
+        for value in &self.data {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(v) = self.difficulty {
+            my_size += ::protobuf::rt::value_size(6, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.merkle_root.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(7, &v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.id {
+            os.write_uint64(1, v)?;
+        }
+        if let Some(v) = self.timestamp {
+            os.write_int64(2, v)?;
+        }
+        if let Some(v) = self.nonce {
+            os.write_uint64(3, v)?;
+        }
+        if let Some(ref v) = self.prev_block_hash.as_ref() {
+            os.write_bytes(4, &v)?;
+        }
+        for v in &self.data {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(v) = self.difficulty {
+            os.write_uint32(6, v)?;
+        }
+        if let Some(ref v) = self.merkle_root.as_ref() {
+            os.write_bytes(7, &v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Block {
+        Block::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "id",
+                    |m: &Block| { &m.id },
+                    |m: &mut Block| { &mut m.id },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "timestamp",
+                    |m: &Block| { &m.timestamp },
+                    |m: &mut Block| { &mut m.timestamp },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "nonce",
+                    |m: &Block| { &m.nonce },
+                    |m: &mut Block| { &mut m.nonce },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "prev_block_hash",
+                    |m: &Block| { &m.prev_block_hash },
+                    |m: &mut Block| { &mut m.prev_block_hash },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Block_Data>>(
+                    "data",
+                    |m: &Block| { &m.data },
+                    |m: &mut Block| { &mut m.data },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "difficulty",
+                    |m: &Block| { &m.difficulty },
+                    |m: &mut Block| { &mut m.difficulty },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "merkle_root",
+                    |m: &Block| { &m.merkle_root },
+                    |m: &mut Block| { &mut m.merkle_root },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Block>(
+                    "Block",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Block {
+        static mut instance: ::protobuf::lazy::Lazy<Block> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Block,
+        };
+        unsafe {
+            instance.get(Block::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Block {
+    fn clear(&mut self) {
+        self.clear_id();
+        self.clear_timestamp();
+        self.clear_nonce();
+        self.clear_prev_block_hash();
+        self.clear_data();
+        self.clear_difficulty();
+        self.clear_merkle_root();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Block {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Block {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Block_Data {
+    // message fields
+    field_type: ::std::option::Option<Block_DataType>,
+    transaction: ::protobuf::SingularPtrField<Transaction>,
+    binaryData: ::protobuf::SingularPtrField<BinaryData>,
+    coinbase: ::protobuf::SingularPtrField<Coinbase>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+impl Block_Data {
+    pub fn new() -> Block_Data {
+        ::std::default::Default::default()
+    }
+
+    // required .Block.DataType type = 1;
+
+    pub fn clear_field_type(&mut self) {
+        self.field_type = ::std::option::Option::None;
+    }
+
+    pub fn has_field_type(&self) -> bool {
+        self.field_type.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_field_type(&mut self, v: Block_DataType) {
+        self.field_type = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_field_type(&self) -> Block_DataType {
+        self.field_type.unwrap_or(Block_DataType::BINARY_DATA)
+    }
+
+    // optional .Transaction transaction = 2;
+
+    pub fn clear_transaction(&mut self) {
+        self.transaction.clear();
+    }
+
+    pub fn has_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_transaction(&mut self, v: Transaction) {
+        self.transaction = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_transaction(&mut self) -> &mut Transaction {
+        if self.transaction.is_none() {
+            self.transaction.set_default();
+        }
+        self.transaction.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_transaction(&mut self) -> Transaction {
+        self.transaction.take().unwrap_or_else(|| Transaction::new())
+    }
+
+    pub fn get_transaction(&self) -> &Transaction {
+        self.transaction.as_ref().unwrap_or_else(|| Transaction::default_instance())
+    }
+
+    // optional .BinaryData binaryData = 3;
+
+    pub fn clear_binaryData(&mut self) {
+        self.binaryData.clear();
+    }
+
+    pub fn has_binaryData(&self) -> bool {
+        self.binaryData.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_binaryData(&mut self, v: BinaryData) {
+        self.binaryData = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_binaryData(&mut self) -> &mut BinaryData {
+        if self.binaryData.is_none() {
+            self.binaryData.set_default();
+        }
+        self.binaryData.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_binaryData(&mut self) -> BinaryData {
+        self.binaryData.take().unwrap_or_else(|| BinaryData::new())
+    }
+
+    pub fn get_binaryData(&self) -> &BinaryData {
+        self.binaryData.as_ref().unwrap_or_else(|| BinaryData::default_instance())
+    }
+
+    // optional .Coinbase coinbase = 4;
+
+    pub fn clear_coinbase(&mut self) {
+        self.coinbase.clear();
+    }
+
+    pub fn has_coinbase(&self) -> bool {
+        self.coinbase.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_coinbase(&mut self, v: Coinbase) {
+        self.coinbase = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_coinbase(&mut self) -> &mut Coinbase {
+        if self.coinbase.is_none() {
+            self.coinbase.set_default();
+        }
+        self.coinbase.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_coinbase(&mut self) -> Coinbase {
+        self.coinbase.take().unwrap_or_else(|| Coinbase::new())
+    }
+
+    pub fn get_coinbase(&self) -> &Coinbase {
+        self.coinbase.as_ref().unwrap_or_else(|| Coinbase::default_instance())
+    }
+}
+
+impl ::protobuf::Message for Block_Data {
+    fn is_initialized(&self) -> bool {
+        if self.field_type.is_none() {
+            return false;
+        }
+        for v in &self.transaction {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.binaryData {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.coinbase {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_proto2_enum_with_unknown_fields_into(wire_type, is, &mut self.field_type, 1, &mut self.unknown_fields)?
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.transaction)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.binaryData)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.coinbase)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.field_type {
+            my_size += ::protobuf::rt::enum_size(1, v);
+        }
+        if let Some(ref v) = self.transaction.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.binaryData.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.coinbase.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.field_type {
+            os.write_enum(1, v.value())?;
+        }
+        if let Some(ref v) = self.transaction.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.binaryData.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.coinbase.as_ref() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Block_Data {
+        Block_Data::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Block_DataType>>(
+                    "type",
+                    |m: &Block_Data| { &m.field_type },
+                    |m: &mut Block_Data| { &mut m.field_type },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Transaction>>(
+                    "transaction",
+                    |m: &Block_Data| { &m.transaction },
+                    |m: &mut Block_Data| { &mut m.transaction },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<BinaryData>>(
+                    "binaryData",
+                    |m: &Block_Data| { &m.binaryData },
+                    |m: &mut Block_Data| { &mut m.binaryData },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Coinbase>>(
+                    "coinbase",
+                    |m: &Block_Data| { &m.coinbase },
+                    |m: &mut Block_Data| { &mut m.coinbase },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Block_Data>(
+                    "Block_Data",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Block_Data {
+        static mut instance: ::protobuf::lazy::Lazy<Block_Data> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Block_Data,
+        };
+        unsafe {
+            instance.get(Block_Data::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Block_Data {
+    fn clear(&mut self) {
+        self.clear_field_type();
+        self.clear_transaction();
+        self.clear_binaryData();
+        self.clear_coinbase();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Block_Data {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Block_Data {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Block_DataType {
+    BINARY_DATA = 0,
+    TRANSACTION = 1,
+    COINBASE = 2,
+}
+
+impl ::protobuf::ProtobufEnum for Block_DataType {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Block_DataType> {
+        match value {
+            0 => ::std::option::Option::Some(Block_DataType::BINARY_DATA),
+            1 => ::std::option::Option::Some(Block_DataType::TRANSACTION),
+            2 => ::std::option::Option::Some(Block_DataType::COINBASE),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [Block_DataType] = &[
+            Block_DataType::BINARY_DATA,
+            Block_DataType::TRANSACTION,
+            Block_DataType::COINBASE,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("Block_DataType", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for Block_DataType {
+}
+
+impl ::protobuf::reflect::ProtobufValue for Block_DataType {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Handshake {
+    // message fields
+    version: ::std::option::Option<u32>,
+    height: ::std::option::Option<u64>,
+    tip_hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+impl Handshake {
+    pub fn new() -> Handshake {
+        ::std::default::Default::default()
+    }
+
+    // required uint32 version = 1;
+
+    pub fn clear_version(&mut self) {
+        self.version = ::std::option::Option::None;
+    }
+
+    pub fn has_version(&self) -> bool {
+        self.version.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_version(&mut self, v: u32) {
+        self.version = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_version(&self) -> u32 {
+        self.version.unwrap_or(0)
+    }
+
+    // required uint64 height = 2;
+
+    pub fn clear_height(&mut self) {
+        self.height = ::std::option::Option::None;
+    }
+
+    pub fn has_height(&self) -> bool {
+        self.height.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_height(&mut self, v: u64) {
+        self.height = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_height(&self) -> u64 {
+        self.height.unwrap_or(0)
+    }
+
+    // required bytes tip_hash = 3;
+
+    pub fn clear_tip_hash(&mut self) {
+        self.tip_hash.clear();
+    }
+
+    pub fn has_tip_hash(&self) -> bool {
+        self.tip_hash.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tip_hash(&mut self, v: ::std::vec::Vec<u8>) {
+        self.tip_hash = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_tip_hash(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.tip_hash.is_none() {
+            self.tip_hash.set_default();
+        }
+        self.tip_hash.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_tip_hash(&mut self) -> ::std::vec::Vec<u8> {
+        self.tip_hash.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_tip_hash(&self) -> &[u8] {
+        match self.tip_hash.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+}
+
+impl ::protobuf::Message for Handshake {
+    fn is_initialized(&self) -> bool {
+        if self.version.is_none() {
+            return false;
+        }
+        if self.height.is_none() {
+            return false;
+        }
+        if self.tip_hash.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.version = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.height = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.tip_hash)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.version {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.height {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.tip_hash.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(3, &v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.version {
+            os.write_uint32(1, v)?;
+        }
+        if let Some(v) = self.height {
+            os.write_uint64(2, v)?;
+        }
+        if let Some(ref v) = self.tip_hash.as_ref() {
+            os.write_bytes(3, &v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Handshake {
+        Handshake::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "version",
+                    |m: &Handshake| { &m.version },
+                    |m: &mut Handshake| { &mut m.version },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "height",
+                    |m: &Handshake| { &m.height },
+                    |m: &mut Handshake| { &mut m.height },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "tip_hash",
+                    |m: &Handshake| { &m.tip_hash },
+                    |m: &mut Handshake| { &mut m.tip_hash },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Handshake>(
+                    "Handshake",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Handshake {
+        static mut instance: ::protobuf::lazy::Lazy<Handshake> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Handshake,
+        };
+        unsafe {
+            instance.get(Handshake::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Handshake {
+    fn clear(&mut self) {
+        self.clear_version();
+        self.clear_height();
+        self.clear_tip_hash();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Handshake {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Handshake {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct GetBlock {
+    // message fields
+    hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+impl GetBlock {
+    pub fn new() -> GetBlock {
+        ::std::default::Default::default()
+    }
+
+    // required bytes hash = 1;
+
+    pub fn clear_hash(&mut self) {
+        self.hash.clear();
+    }
+
+    pub fn has_hash(&self) -> bool {
+        self.hash.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_hash(&mut self, v: ::std::vec::Vec<u8>) {
+        self.hash = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_hash(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.hash.is_none() {
+            self.hash.set_default();
+        }
+        self.hash.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_hash(&mut self) -> ::std::vec::Vec<u8> {
+        self.hash.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_hash(&self) -> &[u8] {
+        match self.hash.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+}
+
+impl ::protobuf::Message for GetBlock {
+    fn is_initialized(&self) -> bool {
+        if self.hash.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.hash)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.hash.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(1, &v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.hash.as_ref() {
+            os.write_bytes(1, &v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> GetBlock {
+        GetBlock::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "hash",
+                    |m: &GetBlock| { &m.hash },
+                    |m: &mut GetBlock| { &mut m.hash },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<GetBlock>(
+                    "GetBlock",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static GetBlock {
+        static mut instance: ::protobuf::lazy::Lazy<GetBlock> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const GetBlock,
+        };
+        unsafe {
+            instance.get(GetBlock::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for GetBlock {
+    fn clear(&mut self) {
+        self.clear_hash();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for GetBlock {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetBlock {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct BlockHeader {
+    // message fields
+    id: ::std::option::Option<u64>,
+    timestamp: ::std::option::Option<i64>,
+    nonce: ::std::option::Option<u64>,
+    prev_block_hash: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    difficulty: ::std::option::Option<u32>,
+    merkle_root: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+impl BlockHeader {
+    pub fn new() -> BlockHeader {
+        ::std::default::Default::default()
+    }
+
+    // required uint64 id = 1;
+
+    pub fn clear_id(&mut self) {
+        self.id = ::std::option::Option::None;
+    }
+
+    pub fn has_id(&self) -> bool {
+        self.id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_id(&mut self, v: u64) {
+        self.id = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_id(&self) -> u64 {
+        self.id.unwrap_or(0)
+    }
+
+    // required int64 timestamp = 2;
+
+    pub fn clear_timestamp(&mut self) {
+        self.timestamp = ::std::option::Option::None;
+    }
+
+    pub fn has_timestamp(&self) -> bool {
+        self.timestamp.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timestamp(&mut self, v: i64) {
+        self.timestamp = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_timestamp(&self) -> i64 {
+        self.timestamp.unwrap_or(0)
+    }
+
+    // required uint64 nonce = 3;
+
+    pub fn clear_nonce(&mut self) {
+        self.nonce = ::std::option::Option::None;
+    }
+
+    pub fn has_nonce(&self) -> bool {
+        self.nonce.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nonce(&mut self, v: u64) {
+        self.nonce = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_nonce(&self) -> u64 {
+        self.nonce.unwrap_or(0)
+    }
+
+    // required bytes prev_block_hash = 4;
+
+    pub fn clear_prev_block_hash(&mut self) {
+        self.prev_block_hash.clear();
+    }
+
+    pub fn has_prev_block_hash(&self) -> bool {
+        self.prev_block_hash.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_prev_block_hash(&mut self, v: ::std::vec::Vec<u8>) {
+        self.prev_block_hash = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_prev_block_hash(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.prev_block_hash.is_none() {
+            self.prev_block_hash.set_default();
+        }
+        self.prev_block_hash.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_prev_block_hash(&mut self) -> ::std::vec::Vec<u8> {
+        self.prev_block_hash.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_prev_block_hash(&self) -> &[u8] {
+        match self.prev_block_hash.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+
+    // required uint32 difficulty = 5;
+
+    pub fn clear_difficulty(&mut self) {
+        self.difficulty = ::std::option::Option::None;
+    }
+
+    pub fn has_difficulty(&self) -> bool {
+        self.difficulty.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_difficulty(&mut self, v: u32) {
+        self.difficulty = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_difficulty(&self) -> u32 {
+        self.difficulty.unwrap_or(0)
+    }
+
+    // required bytes merkle_root = 6;
+
+    pub fn clear_merkle_root(&mut self) {
+        self.merkle_root.clear();
+    }
+
+    pub fn has_merkle_root(&self) -> bool {
+        self.merkle_root.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_merkle_root(&mut self, v: ::std::vec::Vec<u8>) {
+        self.merkle_root = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_merkle_root(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.merkle_root.is_none() {
+            self.merkle_root.set_default();
+        }
+        self.merkle_root.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_merkle_root(&mut self) -> ::std::vec::Vec<u8> {
+        self.merkle_root.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_merkle_root(&self) -> &[u8] {
+        match self.merkle_root.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+}
+
+impl ::protobuf::Message for BlockHeader {
+    fn is_initialized(&self) -> bool {
+        if self.id.is_none() {
+            return false;
+        }
+        if self.timestamp.is_none() {
+            return false;
+        }
+        if self.nonce.is_none() {
+            return false;
+        }
+        if self.prev_block_hash.is_none() {
+            return false;
+        }
+        if self.difficulty.is_none() {
+            return false;
+        }
+        if self.merkle_root.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.id = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.timestamp = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.nonce = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.prev_block_hash)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.difficulty = ::std::option::Option::Some(tmp);
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.merkle_root)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.id {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
         }
         if let Some(v) = self.timestamp {
             my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
         }
-        if let Some(v) = self.nonce {
-            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        if let Some(v) = self.nonce {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.prev_block_hash.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(4, &v);
+        }
+        if let Some(v) = self.difficulty {
+            my_size += ::protobuf::rt::value_size(5, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.merkle_root.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(6, &v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.id {
+            os.write_uint64(1, v)?;
+        }
+        if let Some(v) = self.timestamp {
+            os.write_int64(2, v)?;
+        }
+        if let Some(v) = self.nonce {
+            os.write_uint64(3, v)?;
+        }
+        if let Some(ref v) = self.prev_block_hash.as_ref() {
+            os.write_bytes(4, &v)?;
+        }
+        if let Some(v) = self.difficulty {
+            os.write_uint32(5, v)?;
+        }
+        if let Some(ref v) = self.merkle_root.as_ref() {
+            os.write_bytes(6, &v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> BlockHeader {
+        BlockHeader::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "id",
+                    |m: &BlockHeader| { &m.id },
+                    |m: &mut BlockHeader| { &mut m.id },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "timestamp",
+                    |m: &BlockHeader| { &m.timestamp },
+                    |m: &mut BlockHeader| { &mut m.timestamp },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "nonce",
+                    |m: &BlockHeader| { &m.nonce },
+                    |m: &mut BlockHeader| { &mut m.nonce },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "prev_block_hash",
+                    |m: &BlockHeader| { &m.prev_block_hash },
+                    |m: &mut BlockHeader| { &mut m.prev_block_hash },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "difficulty",
+                    |m: &BlockHeader| { &m.difficulty },
+                    |m: &mut BlockHeader| { &mut m.difficulty },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "merkle_root",
+                    |m: &BlockHeader| { &m.merkle_root },
+                    |m: &mut BlockHeader| { &mut m.merkle_root },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<BlockHeader>(
+                    "BlockHeader",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static BlockHeader {
+        static mut instance: ::protobuf::lazy::Lazy<BlockHeader> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const BlockHeader,
+        };
+        unsafe {
+            instance.get(BlockHeader::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for BlockHeader {
+    fn clear(&mut self) {
+        self.clear_id();
+        self.clear_timestamp();
+        self.clear_nonce();
+        self.clear_prev_block_hash();
+        self.clear_difficulty();
+        self.clear_merkle_root();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for BlockHeader {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BlockHeader {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct GetHeaders {
+    // message fields
+    locator: ::protobuf::RepeatedField<::std::vec::Vec<u8>>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+impl GetHeaders {
+    pub fn new() -> GetHeaders {
+        ::std::default::Default::default()
+    }
+
+    // repeated bytes locator = 1;
+
+    pub fn clear_locator(&mut self) {
+        self.locator.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_locator(&mut self, v: ::protobuf::RepeatedField<::std::vec::Vec<u8>>) {
+        self.locator = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_locator(&mut self) -> &mut ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        &mut self.locator
+    }
+
+    // Take field
+    pub fn take_locator(&mut self) -> ::protobuf::RepeatedField<::std::vec::Vec<u8>> {
+        ::std::mem::replace(&mut self.locator, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_locator(&self) -> &[::std::vec::Vec<u8>] {
+        &self.locator
+    }
+}
+
+impl ::protobuf::Message for GetHeaders {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_bytes_into(wire_type, is, &mut self.locator)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.locator {
+            my_size += ::protobuf::rt::bytes_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.locator {
+            os.write_bytes(1, &v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> GetHeaders {
+        GetHeaders::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "locator",
+                    |m: &GetHeaders| { &m.locator },
+                    |m: &mut GetHeaders| { &mut m.locator },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<GetHeaders>(
+                    "GetHeaders",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static GetHeaders {
+        static mut instance: ::protobuf::lazy::Lazy<GetHeaders> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const GetHeaders,
+        };
+        unsafe {
+            instance.get(GetHeaders::new)
         }
-        if let Some(ref v) = self.prev_block_hash.as_ref() {
-            my_size += ::protobuf::rt::bytes_size(4, &v);
+    }
+}
+
+impl ::protobuf::Clear for GetHeaders {
+    fn clear(&mut self) {
+        self.clear_locator();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for GetHeaders {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GetHeaders {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Headers {
+    // message fields
+    headers: ::protobuf::RepeatedField<BlockHeader>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+impl Headers {
+    pub fn new() -> Headers {
+        ::std::default::Default::default()
+    }
+
+    // repeated .BlockHeader headers = 1;
+
+    pub fn clear_headers(&mut self) {
+        self.headers.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_headers(&mut self, v: ::protobuf::RepeatedField<BlockHeader>) {
+        self.headers = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_headers(&mut self) -> &mut ::protobuf::RepeatedField<BlockHeader> {
+        &mut self.headers
+    }
+
+    // Take field
+    pub fn take_headers(&mut self) -> ::protobuf::RepeatedField<BlockHeader> {
+        ::std::mem::replace(&mut self.headers, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_headers(&self) -> &[BlockHeader] {
+        &self.headers
+    }
+}
+
+impl ::protobuf::Message for Headers {
+    fn is_initialized(&self) -> bool {
+        for v in &self.headers {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.headers)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
         }
-        for value in &self.data {
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.headers {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
@@ -694,20 +2830,8 @@ impl ::protobuf::Message for Block {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
-        if let Some(v) = self.id {
-            os.write_uint64(1, v)?;
-        }
-        if let Some(v) = self.timestamp {
-            os.write_int64(2, v)?;
-        }
-        if let Some(v) = self.nonce {
-            os.write_uint64(3, v)?;
-        }
-        if let Some(ref v) = self.prev_block_hash.as_ref() {
-            os.write_bytes(4, &v)?;
-        }
-        for v in &self.data {
-            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+        for v in &self.headers {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         };
@@ -741,8 +2865,8 @@ impl ::protobuf::Message for Block {
         Self::descriptor_static()
     }
 
-    fn new() -> Block {
-        Block::new()
+    fn new() -> Headers {
+        Headers::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -753,33 +2877,13 @@ impl ::protobuf::Message for Block {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
-                    "id",
-                    |m: &Block| { &m.id },
-                    |m: &mut Block| { &mut m.id },
-                ));
-                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
-                    "timestamp",
-                    |m: &Block| { &m.timestamp },
-                    |m: &mut Block| { &mut m.timestamp },
-                ));
-                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
-                    "nonce",
-                    |m: &Block| { &m.nonce },
-                    |m: &mut Block| { &mut m.nonce },
-                ));
-                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
-                    "prev_block_hash",
-                    |m: &Block| { &m.prev_block_hash },
-                    |m: &mut Block| { &mut m.prev_block_hash },
-                ));
-                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Block_Data>>(
-                    "data",
-                    |m: &Block| { &m.data },
-                    |m: &mut Block| { &mut m.data },
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<BlockHeader>>(
+                    "headers",
+                    |m: &Headers| { &m.headers },
+                    |m: &mut Headers| { &mut m.headers },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new::<Block>(
-                    "Block",
+                ::protobuf::reflect::MessageDescriptor::new::<Headers>(
+                    "Headers",
                     fields,
                     file_descriptor_proto()
                 )
@@ -787,57 +2891,57 @@ impl ::protobuf::Message for Block {
         }
     }
 
-    fn default_instance() -> &'static Block {
-        static mut instance: ::protobuf::lazy::Lazy<Block> = ::protobuf::lazy::Lazy {
+    fn default_instance() -> &'static Headers {
+        static mut instance: ::protobuf::lazy::Lazy<Headers> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const Block,
+            ptr: 0 as *const Headers,
         };
         unsafe {
-            instance.get(Block::new)
+            instance.get(Headers::new)
         }
     }
 }
 
-impl ::protobuf::Clear for Block {
+impl ::protobuf::Clear for Headers {
     fn clear(&mut self) {
-        self.clear_id();
-        self.clear_timestamp();
-        self.clear_nonce();
-        self.clear_prev_block_hash();
-        self.clear_data();
+        self.clear_headers();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for Block {
+impl ::std::fmt::Debug for Headers {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Block {
+impl ::protobuf::reflect::ProtobufValue for Headers {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct Block_Data {
+pub struct Envelope {
     // message fields
-    field_type: ::std::option::Option<Block_DataType>,
+    field_type: ::std::option::Option<Envelope_MessageType>,
+    handshake: ::protobuf::SingularPtrField<Handshake>,
+    block: ::protobuf::SingularPtrField<Block>,
     transaction: ::protobuf::SingularPtrField<Transaction>,
-    binaryData: ::protobuf::SingularPtrField<BinaryData>,
+    getBlock: ::protobuf::SingularPtrField<GetBlock>,
+    getHeaders: ::protobuf::SingularPtrField<GetHeaders>,
+    headers: ::protobuf::SingularPtrField<Headers>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
 }
 
-impl Block_Data {
-    pub fn new() -> Block_Data {
+impl Envelope {
+    pub fn new() -> Envelope {
         ::std::default::Default::default()
     }
 
-    // required .Block.DataType type = 1;
+    // required .Envelope.MessageType type = 1;
 
     pub fn clear_field_type(&mut self) {
         self.field_type = ::std::option::Option::None;
@@ -848,15 +2952,81 @@ impl Block_Data {
     }
 
     // Param is passed by value, moved
-    pub fn set_field_type(&mut self, v: Block_DataType) {
+    pub fn set_field_type(&mut self, v: Envelope_MessageType) {
         self.field_type = ::std::option::Option::Some(v);
     }
 
-    pub fn get_field_type(&self) -> Block_DataType {
-        self.field_type.unwrap_or(Block_DataType::BINARY_DATA)
+    pub fn get_field_type(&self) -> Envelope_MessageType {
+        self.field_type.unwrap_or(Envelope_MessageType::HANDSHAKE)
     }
 
-    // optional .Transaction transaction = 2;
+    // optional .Handshake handshake = 2;
+
+    pub fn clear_handshake(&mut self) {
+        self.handshake.clear();
+    }
+
+    pub fn has_handshake(&self) -> bool {
+        self.handshake.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_handshake(&mut self, v: Handshake) {
+        self.handshake = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_handshake(&mut self) -> &mut Handshake {
+        if self.handshake.is_none() {
+            self.handshake.set_default();
+        }
+        self.handshake.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_handshake(&mut self) -> Handshake {
+        self.handshake.take().unwrap_or_else(|| Handshake::new())
+    }
+
+    pub fn get_handshake(&self) -> &Handshake {
+        self.handshake.as_ref().unwrap_or_else(|| Handshake::default_instance())
+    }
+
+    // optional .Block block = 3;
+
+    pub fn clear_block(&mut self) {
+        self.block.clear();
+    }
+
+    pub fn has_block(&self) -> bool {
+        self.block.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_block(&mut self, v: Block) {
+        self.block = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_block(&mut self) -> &mut Block {
+        if self.block.is_none() {
+            self.block.set_default();
+        }
+        self.block.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_block(&mut self) -> Block {
+        self.block.take().unwrap_or_else(|| Block::new())
+    }
+
+    pub fn get_block(&self) -> &Block {
+        self.block.as_ref().unwrap_or_else(|| Block::default_instance())
+    }
+
+    // optional .Transaction transaction = 4;
 
     pub fn clear_transaction(&mut self) {
         self.transaction.clear();
@@ -889,51 +3059,137 @@ impl Block_Data {
         self.transaction.as_ref().unwrap_or_else(|| Transaction::default_instance())
     }
 
-    // optional .BinaryData binaryData = 3;
+    // optional .GetBlock getBlock = 5;
 
-    pub fn clear_binaryData(&mut self) {
-        self.binaryData.clear();
+    pub fn clear_getBlock(&mut self) {
+        self.getBlock.clear();
     }
 
-    pub fn has_binaryData(&self) -> bool {
-        self.binaryData.is_some()
+    pub fn has_getBlock(&self) -> bool {
+        self.getBlock.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_getBlock(&mut self, v: GetBlock) {
+        self.getBlock = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_getBlock(&mut self) -> &mut GetBlock {
+        if self.getBlock.is_none() {
+            self.getBlock.set_default();
+        }
+        self.getBlock.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_getBlock(&mut self) -> GetBlock {
+        self.getBlock.take().unwrap_or_else(|| GetBlock::new())
+    }
+
+    pub fn get_getBlock(&self) -> &GetBlock {
+        self.getBlock.as_ref().unwrap_or_else(|| GetBlock::default_instance())
+    }
+
+    // optional .GetHeaders getHeaders = 6;
+
+    pub fn clear_getHeaders(&mut self) {
+        self.getHeaders.clear();
+    }
+
+    pub fn has_getHeaders(&self) -> bool {
+        self.getHeaders.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_getHeaders(&mut self, v: GetHeaders) {
+        self.getHeaders = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_getHeaders(&mut self) -> &mut GetHeaders {
+        if self.getHeaders.is_none() {
+            self.getHeaders.set_default();
+        }
+        self.getHeaders.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_getHeaders(&mut self) -> GetHeaders {
+        self.getHeaders.take().unwrap_or_else(|| GetHeaders::new())
+    }
+
+    pub fn get_getHeaders(&self) -> &GetHeaders {
+        self.getHeaders.as_ref().unwrap_or_else(|| GetHeaders::default_instance())
+    }
+
+    // optional .Headers headers = 7;
+
+    pub fn clear_headers(&mut self) {
+        self.headers.clear();
+    }
+
+    pub fn has_headers(&self) -> bool {
+        self.headers.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_binaryData(&mut self, v: BinaryData) {
-        self.binaryData = ::protobuf::SingularPtrField::some(v);
+    pub fn set_headers(&mut self, v: Headers) {
+        self.headers = ::protobuf::SingularPtrField::some(v);
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_binaryData(&mut self) -> &mut BinaryData {
-        if self.binaryData.is_none() {
-            self.binaryData.set_default();
+    pub fn mut_headers(&mut self) -> &mut Headers {
+        if self.headers.is_none() {
+            self.headers.set_default();
         }
-        self.binaryData.as_mut().unwrap()
+        self.headers.as_mut().unwrap()
     }
 
     // Take field
-    pub fn take_binaryData(&mut self) -> BinaryData {
-        self.binaryData.take().unwrap_or_else(|| BinaryData::new())
+    pub fn take_headers(&mut self) -> Headers {
+        self.headers.take().unwrap_or_else(|| Headers::new())
     }
 
-    pub fn get_binaryData(&self) -> &BinaryData {
-        self.binaryData.as_ref().unwrap_or_else(|| BinaryData::default_instance())
+    pub fn get_headers(&self) -> &Headers {
+        self.headers.as_ref().unwrap_or_else(|| Headers::default_instance())
     }
 }
 
-impl ::protobuf::Message for Block_Data {
+impl ::protobuf::Message for Envelope {
     fn is_initialized(&self) -> bool {
         if self.field_type.is_none() {
             return false;
         }
+        for v in &self.handshake {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.block {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         for v in &self.transaction {
             if !v.is_initialized() {
                 return false;
             }
         };
-        for v in &self.binaryData {
+        for v in &self.getBlock {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.getHeaders {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.headers {
             if !v.is_initialized() {
                 return false;
             }
@@ -949,10 +3205,22 @@ impl ::protobuf::Message for Block_Data {
                     ::protobuf::rt::read_proto2_enum_with_unknown_fields_into(wire_type, is, &mut self.field_type, 1, &mut self.unknown_fields)?
                 },
                 2 => {
-                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.transaction)?;
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.handshake)?;
                 },
                 3 => {
-                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.binaryData)?;
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.block)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.transaction)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.getBlock)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.getHeaders)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.headers)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -969,11 +3237,27 @@ impl ::protobuf::Message for Block_Data {
         if let Some(v) = self.field_type {
             my_size += ::protobuf::rt::enum_size(1, v);
         }
+        if let Some(ref v) = self.handshake.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.block.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
         if let Some(ref v) = self.transaction.as_ref() {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
-        if let Some(ref v) = self.binaryData.as_ref() {
+        if let Some(ref v) = self.getBlock.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.getHeaders.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.headers.as_ref() {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
@@ -986,16 +3270,36 @@ impl ::protobuf::Message for Block_Data {
         if let Some(v) = self.field_type {
             os.write_enum(1, v.value())?;
         }
-        if let Some(ref v) = self.transaction.as_ref() {
+        if let Some(ref v) = self.handshake.as_ref() {
             os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
-        if let Some(ref v) = self.binaryData.as_ref() {
+        if let Some(ref v) = self.block.as_ref() {
             os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
+        if let Some(ref v) = self.transaction.as_ref() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.getBlock.as_ref() {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.getHeaders.as_ref() {
+            os.write_tag(6, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.headers.as_ref() {
+            os.write_tag(7, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1026,8 +3330,8 @@ impl ::protobuf::Message for Block_Data {
         Self::descriptor_static()
     }
 
-    fn new() -> Block_Data {
-        Block_Data::new()
+    fn new() -> Envelope {
+        Envelope::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -1038,23 +3342,43 @@ impl ::protobuf::Message for Block_Data {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Block_DataType>>(
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Envelope_MessageType>>(
                     "type",
-                    |m: &Block_Data| { &m.field_type },
-                    |m: &mut Block_Data| { &mut m.field_type },
+                    |m: &Envelope| { &m.field_type },
+                    |m: &mut Envelope| { &mut m.field_type },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Handshake>>(
+                    "handshake",
+                    |m: &Envelope| { &m.handshake },
+                    |m: &mut Envelope| { &mut m.handshake },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Block>>(
+                    "block",
+                    |m: &Envelope| { &m.block },
+                    |m: &mut Envelope| { &mut m.block },
                 ));
                 fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Transaction>>(
                     "transaction",
-                    |m: &Block_Data| { &m.transaction },
-                    |m: &mut Block_Data| { &mut m.transaction },
+                    |m: &Envelope| { &m.transaction },
+                    |m: &mut Envelope| { &mut m.transaction },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<BinaryData>>(
-                    "binaryData",
-                    |m: &Block_Data| { &m.binaryData },
-                    |m: &mut Block_Data| { &mut m.binaryData },
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<GetBlock>>(
+                    "getBlock",
+                    |m: &Envelope| { &m.getBlock },
+                    |m: &mut Envelope| { &mut m.getBlock },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new::<Block_Data>(
-                    "Block_Data",
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<GetHeaders>>(
+                    "getHeaders",
+                    |m: &Envelope| { &m.getHeaders },
+                    |m: &mut Envelope| { &mut m.getHeaders },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Headers>>(
+                    "headers",
+                    |m: &Envelope| { &m.headers },
+                    |m: &mut Envelope| { &mut m.headers },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Envelope>(
+                    "Envelope",
                     fields,
                     file_descriptor_proto()
                 )
@@ -1062,61 +3386,77 @@ impl ::protobuf::Message for Block_Data {
         }
     }
 
-    fn default_instance() -> &'static Block_Data {
-        static mut instance: ::protobuf::lazy::Lazy<Block_Data> = ::protobuf::lazy::Lazy {
+    fn default_instance() -> &'static Envelope {
+        static mut instance: ::protobuf::lazy::Lazy<Envelope> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const Block_Data,
+            ptr: 0 as *const Envelope,
         };
         unsafe {
-            instance.get(Block_Data::new)
+            instance.get(Envelope::new)
         }
     }
 }
 
-impl ::protobuf::Clear for Block_Data {
+impl ::protobuf::Clear for Envelope {
     fn clear(&mut self) {
         self.clear_field_type();
+        self.clear_handshake();
+        self.clear_block();
         self.clear_transaction();
-        self.clear_binaryData();
+        self.clear_getBlock();
+        self.clear_getHeaders();
+        self.clear_headers();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for Block_Data {
+impl ::std::fmt::Debug for Envelope {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Block_Data {
+impl ::protobuf::reflect::ProtobufValue for Envelope {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Message(self)
     }
 }
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
-pub enum Block_DataType {
-    BINARY_DATA = 0,
-    TRANSACTION = 1,
+pub enum Envelope_MessageType {
+    HANDSHAKE = 0,
+    BLOCK = 1,
+    TRANSACTION = 2,
+    GET_BLOCK = 3,
+    GET_HEADERS = 4,
+    HEADERS = 5,
 }
 
-impl ::protobuf::ProtobufEnum for Block_DataType {
+impl ::protobuf::ProtobufEnum for Envelope_MessageType {
     fn value(&self) -> i32 {
         *self as i32
     }
 
-    fn from_i32(value: i32) -> ::std::option::Option<Block_DataType> {
+    fn from_i32(value: i32) -> ::std::option::Option<Envelope_MessageType> {
         match value {
-            0 => ::std::option::Option::Some(Block_DataType::BINARY_DATA),
-            1 => ::std::option::Option::Some(Block_DataType::TRANSACTION),
+            0 => ::std::option::Option::Some(Envelope_MessageType::HANDSHAKE),
+            1 => ::std::option::Option::Some(Envelope_MessageType::BLOCK),
+            2 => ::std::option::Option::Some(Envelope_MessageType::TRANSACTION),
+            3 => ::std::option::Option::Some(Envelope_MessageType::GET_BLOCK),
+            4 => ::std::option::Option::Some(Envelope_MessageType::GET_HEADERS),
+            5 => ::std::option::Option::Some(Envelope_MessageType::HEADERS),
             _ => ::std::option::Option::None
         }
     }
 
     fn values() -> &'static [Self] {
-        static values: &'static [Block_DataType] = &[
-            Block_DataType::BINARY_DATA,
-            Block_DataType::TRANSACTION,
+        static values: &'static [Envelope_MessageType] = &[
+            Envelope_MessageType::HANDSHAKE,
+            Envelope_MessageType::BLOCK,
+            Envelope_MessageType::TRANSACTION,
+            Envelope_MessageType::GET_BLOCK,
+            Envelope_MessageType::GET_HEADERS,
+            Envelope_MessageType::HEADERS,
         ];
         values
     }
@@ -1128,89 +3468,411 @@ impl ::protobuf::ProtobufEnum for Block_DataType {
         };
         unsafe {
             descriptor.get(|| {
-                ::protobuf::reflect::EnumDescriptor::new("Block_DataType", file_descriptor_proto())
+                ::protobuf::reflect::EnumDescriptor::new("Envelope_MessageType", file_descriptor_proto())
             })
         }
     }
 }
 
-impl ::std::marker::Copy for Block_DataType {
+impl ::std::marker::Copy for Envelope_MessageType {
 }
 
-impl ::protobuf::reflect::ProtobufValue for Block_DataType {
+impl ::protobuf::reflect::ProtobufValue for Envelope_MessageType {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct Mempool {
+    // message fields
+    data: ::protobuf::RepeatedField<Block_Data>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+impl Mempool {
+    pub fn new() -> Mempool {
+        ::std::default::Default::default()
+    }
+
+    // repeated .Block.Data data = 1;
+
+    pub fn clear_data(&mut self) {
+        self.data.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_data(&mut self, v: ::protobuf::RepeatedField<Block_Data>) {
+        self.data = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_data(&mut self) -> &mut ::protobuf::RepeatedField<Block_Data> {
+        &mut self.data
+    }
+
+    // Take field
+    pub fn take_data(&mut self) -> ::protobuf::RepeatedField<Block_Data> {
+        ::std::mem::replace(&mut self.data, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_data(&self) -> &[Block_Data] {
+        &self.data
+    }
+}
+
+impl ::protobuf::Message for Mempool {
+    fn is_initialized(&self) -> bool {
+        for v in &self.data {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.data)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.data {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.data {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Mempool {
+        Mempool::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Block_Data>>(
+                    "data",
+                    |m: &Mempool| { &m.data },
+                    |m: &mut Mempool| { &mut m.data },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Mempool>(
+                    "Mempool",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Mempool {
+        static mut instance: ::protobuf::lazy::Lazy<Mempool> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Mempool,
+        };
+        unsafe {
+            instance.get(Mempool::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Mempool {
+    fn clear(&mut self) {
+        self.clear_data();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Mempool {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Mempool {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x15src/proto/chain.proto\"I\n\x0bTransaction\x12\x12\n\x04from\x18\
-    \x01\x20\x02(\x0cR\x04from\x12\x0e\n\x02to\x18\x02\x20\x02(\x0cR\x02to\
-    \x12\x16\n\x06amount\x18\x03\x20\x02(\x04R\x06amount\"\x20\n\nBinaryData\
-    \x12\x12\n\x04data\x18\x01\x20\x02(\x0cR\x04data\"\xcd\x02\n\x05Block\
-    \x12\x0e\n\x02id\x18\x01\x20\x02(\x04R\x02id\x12\x1c\n\ttimestamp\x18\
-    \x02\x20\x02(\x03R\ttimestamp\x12\x14\n\x05nonce\x18\x03\x20\x02(\x04R\
-    \x05nonce\x12&\n\x0fprev_block_hash\x18\x04\x20\x02(\x0cR\rprevBlockHash\
-    \x12\x1f\n\x04data\x18\x05\x20\x03(\x0b2\x0b.Block.DataR\x04data\x1a\x88\
-    \x01\n\x04Data\x12#\n\x04type\x18\x01\x20\x02(\x0e2\x0f.Block.DataTypeR\
-    \x04type\x12.\n\x0btransaction\x18\x02\x20\x01(\x0b2\x0c.TransactionR\
-    \x0btransaction\x12+\n\nbinaryData\x18\x03\x20\x01(\x0b2\x0b.BinaryDataR\
-    \nbinaryData\",\n\x08DataType\x12\x0f\n\x0bBINARY_DATA\x10\0\x12\x0f\n\
-    \x0bTRANSACTION\x10\x01J\xca\x08\n\x06\x12\x04\0\0\x1d\x01\n\x08\n\x01\
-    \x0c\x12\x03\0\0\x12\n\n\n\x02\x04\0\x12\x04\x02\0\x06\x01\n\n\n\x03\x04\
-    \0\x01\x12\x03\x02\x08\x13\n\x0b\n\x04\x04\0\x02\0\x12\x03\x03\x02\x1a\n\
-    \x0c\n\x05\x04\0\x02\0\x04\x12\x03\x03\x02\n\n\x0c\n\x05\x04\0\x02\0\x05\
-    \x12\x03\x03\x0b\x10\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x03\x11\x15\n\
-    \x0c\n\x05\x04\0\x02\0\x03\x12\x03\x03\x18\x19\n\x0b\n\x04\x04\0\x02\x01\
-    \x12\x03\x04\x02\x18\n\x0c\n\x05\x04\0\x02\x01\x04\x12\x03\x04\x02\n\n\
-    \x0c\n\x05\x04\0\x02\x01\x05\x12\x03\x04\x0b\x10\n\x0c\n\x05\x04\0\x02\
-    \x01\x01\x12\x03\x04\x11\x13\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x04\
-    \x16\x17\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x05\x02\x1d\n\x0c\n\x05\x04\0\
-    \x02\x02\x04\x12\x03\x05\x02\n\n\x0c\n\x05\x04\0\x02\x02\x05\x12\x03\x05\
-    \x0b\x11\n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x05\x12\x18\n\x0c\n\x05\
-    \x04\0\x02\x02\x03\x12\x03\x05\x1b\x1c\n\n\n\x02\x04\x01\x12\x04\x08\0\n\
-    \x01\n\n\n\x03\x04\x01\x01\x12\x03\x08\x08\x12\n\x0b\n\x04\x04\x01\x02\0\
-    \x12\x03\t\x02\x1a\n\x0c\n\x05\x04\x01\x02\0\x04\x12\x03\t\x02\n\n\x0c\n\
-    \x05\x04\x01\x02\0\x05\x12\x03\t\x0b\x10\n\x0c\n\x05\x04\x01\x02\0\x01\
-    \x12\x03\t\x11\x15\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\t\x18\x19\n\n\n\
-    \x02\x04\x02\x12\x04\x0c\0\x1d\x01\n\n\n\x03\x04\x02\x01\x12\x03\x0c\x08\
-    \r\n\x0c\n\x04\x04\x02\x04\0\x12\x04\r\x02\x10\x03\n\x0c\n\x05\x04\x02\
-    \x04\0\x01\x12\x03\r\x07\x0f\n\r\n\x06\x04\x02\x04\0\x02\0\x12\x03\x0e\
-    \x04\x14\n\x0e\n\x07\x04\x02\x04\0\x02\0\x01\x12\x03\x0e\x04\x0f\n\x0e\n\
-    \x07\x04\x02\x04\0\x02\0\x02\x12\x03\x0e\x12\x13\n\r\n\x06\x04\x02\x04\0\
-    \x02\x01\x12\x03\x0f\x04\x14\n\x0e\n\x07\x04\x02\x04\0\x02\x01\x01\x12\
-    \x03\x0f\x04\x0f\n\x0e\n\x07\x04\x02\x04\0\x02\x01\x02\x12\x03\x0f\x12\
-    \x13\n\x0c\n\x04\x04\x02\x03\0\x12\x04\x12\x02\x16\x03\n\x0c\n\x05\x04\
-    \x02\x03\0\x01\x12\x03\x12\n\x0e\n\r\n\x06\x04\x02\x03\0\x02\0\x12\x03\
-    \x13\x04\x1f\n\x0e\n\x07\x04\x02\x03\0\x02\0\x04\x12\x03\x13\x04\x0c\n\
-    \x0e\n\x07\x04\x02\x03\0\x02\0\x06\x12\x03\x13\r\x15\n\x0e\n\x07\x04\x02\
-    \x03\0\x02\0\x01\x12\x03\x13\x16\x1a\n\x0e\n\x07\x04\x02\x03\0\x02\0\x03\
-    \x12\x03\x13\x1d\x1e\n\r\n\x06\x04\x02\x03\0\x02\x01\x12\x03\x14\x04)\n\
-    \x0e\n\x07\x04\x02\x03\0\x02\x01\x04\x12\x03\x14\x04\x0c\n\x0e\n\x07\x04\
-    \x02\x03\0\x02\x01\x06\x12\x03\x14\r\x18\n\x0e\n\x07\x04\x02\x03\0\x02\
-    \x01\x01\x12\x03\x14\x19$\n\x0e\n\x07\x04\x02\x03\0\x02\x01\x03\x12\x03\
-    \x14'(\n\r\n\x06\x04\x02\x03\0\x02\x02\x12\x03\x15\x04'\n\x0e\n\x07\x04\
-    \x02\x03\0\x02\x02\x04\x12\x03\x15\x04\x0c\n\x0e\n\x07\x04\x02\x03\0\x02\
-    \x02\x06\x12\x03\x15\r\x17\n\x0e\n\x07\x04\x02\x03\0\x02\x02\x01\x12\x03\
-    \x15\x18\"\n\x0e\n\x07\x04\x02\x03\0\x02\x02\x03\x12\x03\x15%&\n\x0b\n\
-    \x04\x04\x02\x02\0\x12\x03\x18\x02\x19\n\x0c\n\x05\x04\x02\x02\0\x04\x12\
-    \x03\x18\x02\n\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03\x18\x0b\x11\n\x0c\n\
-    \x05\x04\x02\x02\0\x01\x12\x03\x18\x12\x14\n\x0c\n\x05\x04\x02\x02\0\x03\
-    \x12\x03\x18\x17\x18\n\x0b\n\x04\x04\x02\x02\x01\x12\x03\x19\x02\x1f\n\
-    \x0c\n\x05\x04\x02\x02\x01\x04\x12\x03\x19\x02\n\n\x0c\n\x05\x04\x02\x02\
-    \x01\x05\x12\x03\x19\x0b\x10\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x19\
-    \x11\x1a\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\x19\x1d\x1e\n\x0b\n\x04\
-    \x04\x02\x02\x02\x12\x03\x1a\x02\x1c\n\x0c\n\x05\x04\x02\x02\x02\x04\x12\
-    \x03\x1a\x02\n\n\x0c\n\x05\x04\x02\x02\x02\x05\x12\x03\x1a\x0b\x11\n\x0c\
-    \n\x05\x04\x02\x02\x02\x01\x12\x03\x1a\x12\x17\n\x0c\n\x05\x04\x02\x02\
-    \x02\x03\x12\x03\x1a\x1a\x1b\n\x0b\n\x04\x04\x02\x02\x03\x12\x03\x1b\x02\
-    %\n\x0c\n\x05\x04\x02\x02\x03\x04\x12\x03\x1b\x02\n\n\x0c\n\x05\x04\x02\
-    \x02\x03\x05\x12\x03\x1b\x0b\x10\n\x0c\n\x05\x04\x02\x02\x03\x01\x12\x03\
-    \x1b\x11\x20\n\x0c\n\x05\x04\x02\x02\x03\x03\x12\x03\x1b#$\n\x0b\n\x04\
-    \x04\x02\x02\x04\x12\x03\x1c\x02\x19\n\x0c\n\x05\x04\x02\x02\x04\x04\x12\
-    \x03\x1c\x02\n\n\x0c\n\x05\x04\x02\x02\x04\x06\x12\x03\x1c\x0b\x0f\n\x0c\
-    \n\x05\x04\x02\x02\x04\x01\x12\x03\x1c\x10\x14\n\x0c\n\x05\x04\x02\x02\
-    \x04\x03\x12\x03\x1c\x17\x18\
+    \n\x15src/proto/chain.proto\"\xae\x01\n\x0bTransaction\x12\x12\n\x04from\
+    \x18\x01\x20\x02(\x0cR\x04from\x12\x0e\n\x02to\x18\x02\x20\x02(\x0cR\x02\
+    to\x12\x16\n\x06amount\x18\x03\x20\x02(\x04R\x06amount\x12\x1d\n\npublic\
+    _key\x18\x04\x20\x02(\x0cR\tpublicKey\x12\x1c\n\tsignature\x18\x05\x20\
+    \x02(\x0cR\tsignature\x12\x10\n\x03fee\x18\x06\x20\x02(\x04R\x03fee\x12\
+    \x14\n\x05nonce\x18\x07\x20\x02(\x04R\x05nonce\"\x20\n\nBinaryData\x12\
+    \x12\n\x04data\x18\x01\x20\x02(\x0cR\x04data\"X\n\x08Coinbase\x12\x1c\n\
+    \trecipient\x18\x01\x20\x02(\x0cR\trecipient\x12\x16\n\x06amount\x18\x02\
+    \x20\x02(\x04R\x06amount\x12\x16\n\x06height\x18\x03\x20\x02(\x04R\x06he\
+    ight\"\xc3\x03\n\x05Block\x12\x0e\n\x02id\x18\x01\x20\x02(\x04R\x02id\
+    \x12\x1c\n\ttimestamp\x18\x02\x20\x02(\x03R\ttimestamp\x12\x14\n\x05nonc\
+    e\x18\x03\x20\x02(\x04R\x05nonce\x12&\n\x0fprev_block_hash\x18\x04\x20\
+    \x02(\x0cR\rprevBlockHash\x12\x1f\n\x04data\x18\x05\x20\x03(\x0b2\x0b.Bl\
+    ock.DataR\x04data\x12\x1e\n\ndifficulty\x18\x06\x20\x02(\rR\ndifficulty\
+    \x12\x1f\n\x0bmerkle_root\x18\x07\x20\x02(\x0cR\nmerkleRoot\x1a\xaf\x01\
+    \n\x04Data\x12#\n\x04type\x18\x01\x20\x02(\x0e2\x0f.Block.DataTypeR\x04t\
+    ype\x12.\n\x0btransaction\x18\x02\x20\x01(\x0b2\x0c.TransactionR\x0btran\
+    saction\x12+\n\nbinaryData\x18\x03\x20\x01(\x0b2\x0b.BinaryDataR\nbinary\
+    Data\x12%\n\x08coinbase\x18\x04\x20\x01(\x0b2\t.CoinbaseR\x08coinbase\":\
+    \n\x08DataType\x12\x0f\n\x0bBINARY_DATA\x10\0\x12\x0f\n\x0bTRANSACTION\
+    \x10\x01\x12\x0c\n\x08COINBASE\x10\x02\"X\n\tHandshake\x12\x18\n\x07vers\
+    ion\x18\x01\x20\x02(\rR\x07version\x12\x16\n\x06height\x18\x02\x20\x02(\
+    \x04R\x06height\x12\x19\n\x08tip_hash\x18\x03\x20\x02(\x0cR\x07tipHash\"\
+    \x1e\n\x08GetBlock\x12\x12\n\x04hash\x18\x01\x20\x02(\x0cR\x04hash\"\xba\
+    \x01\n\x0bBlockHeader\x12\x0e\n\x02id\x18\x01\x20\x02(\x04R\x02id\x12\
+    \x1c\n\ttimestamp\x18\x02\x20\x02(\x03R\ttimestamp\x12\x14\n\x05nonce\
+    \x18\x03\x20\x02(\x04R\x05nonce\x12&\n\x0fprev_block_hash\x18\x04\x20\
+    \x02(\x0cR\rprevBlockHash\x12\x1e\n\ndifficulty\x18\x05\x20\x02(\rR\ndif\
+    ficulty\x12\x1f\n\x0bmerkle_root\x18\x06\x20\x02(\x0cR\nmerkleRoot\"&\n\
+    \nGetHeaders\x12\x18\n\x07locator\x18\x01\x20\x03(\x0cR\x07locator\"1\n\
+    \x07Headers\x12&\n\x07headers\x18\x01\x20\x03(\x0b2\x0c.BlockHeaderR\x07\
+    headers\"\x8c\x03\n\x08Envelope\x12)\n\x04type\x18\x01\x20\x02(\x0e2\x15\
+    .Envelope.MessageTypeR\x04type\x12(\n\thandshake\x18\x02\x20\x01(\x0b2\n\
+    .HandshakeR\thandshake\x12\x1c\n\x05block\x18\x03\x20\x01(\x0b2\x06.Bloc\
+    kR\x05block\x12.\n\x0btransaction\x18\x04\x20\x01(\x0b2\x0c.TransactionR\
+    \x0btransaction\x12%\n\x08getBlock\x18\x05\x20\x01(\x0b2\t.GetBlockR\x08\
+    getBlock\x12+\n\ngetHeaders\x18\x06\x20\x01(\x0b2\x0b.GetHeadersR\ngetHe\
+    aders\x12\"\n\x07headers\x18\x07\x20\x01(\x0b2\x08.HeadersR\x07headers\"\
+    e\n\x0bMessageType\x12\r\n\tHANDSHAKE\x10\0\x12\t\n\x05BLOCK\x10\x01\x12\
+    \x0f\n\x0bTRANSACTION\x10\x02\x12\r\n\tGET_BLOCK\x10\x03\x12\x0f\n\x0bGE\
+    T_HEADERS\x10\x04\x12\x0b\n\x07HEADERS\x10\x05\"*\n\x07Mempool\x12\x1f\n\
+    \x04data\x18\x01\x20\x03(\x0b2\x0b.Block.DataR\x04dataJ\x8f\x1d\n\x06\
+    \x12\x04\0\0\\\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\n\n\x02\x04\0\x12\
+    \x04\x02\0\n\x01\n\n\n\x03\x04\0\x01\x12\x03\x02\x08\x13\n\x0b\n\x04\x04\
+    \0\x02\0\x12\x03\x03\x02\x1a\n\x0c\n\x05\x04\0\x02\0\x04\x12\x03\x03\x02\
+    \n\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x03\x0b\x10\n\x0c\n\x05\x04\0\x02\
+    \0\x01\x12\x03\x03\x11\x15\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x03\x18\
+    \x19\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x04\x02\x18\n\x0c\n\x05\x04\0\x02\
+    \x01\x04\x12\x03\x04\x02\n\n\x0c\n\x05\x04\0\x02\x01\x05\x12\x03\x04\x0b\
+    \x10\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x04\x11\x13\n\x0c\n\x05\x04\0\
+    \x02\x01\x03\x12\x03\x04\x16\x17\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x05\
+    \x02\x1d\n\x0c\n\x05\x04\0\x02\x02\x04\x12\x03\x05\x02\n\n\x0c\n\x05\x04\
+    \0\x02\x02\x05\x12\x03\x05\x0b\x11\n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\
+    \x05\x12\x18\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\x05\x1b\x1c\n\x0b\n\
+    \x04\x04\0\x02\x03\x12\x03\x06\x02\x20\n\x0c\n\x05\x04\0\x02\x03\x04\x12\
+    \x03\x06\x02\n\n\x0c\n\x05\x04\0\x02\x03\x05\x12\x03\x06\x0b\x10\n\x0c\n\
+    \x05\x04\0\x02\x03\x01\x12\x03\x06\x11\x1b\n\x0c\n\x05\x04\0\x02\x03\x03\
+    \x12\x03\x06\x1e\x1f\n\x0b\n\x04\x04\0\x02\x04\x12\x03\x07\x02\x1f\n\x0c\
+    \n\x05\x04\0\x02\x04\x04\x12\x03\x07\x02\n\n\x0c\n\x05\x04\0\x02\x04\x05\
+    \x12\x03\x07\x0b\x10\n\x0c\n\x05\x04\0\x02\x04\x01\x12\x03\x07\x11\x1a\n\
+    \x0c\n\x05\x04\0\x02\x04\x03\x12\x03\x07\x1d\x1e\n\x0b\n\x04\x04\0\x02\
+    \x05\x12\x03\x08\x02\x1a\n\x0c\n\x05\x04\0\x02\x05\x04\x12\x03\x08\x02\n\
+    \n\x0c\n\x05\x04\0\x02\x05\x05\x12\x03\x08\x0b\x11\n\x0c\n\x05\x04\0\x02\
+    \x05\x01\x12\x03\x08\x12\x15\n\x0c\n\x05\x04\0\x02\x05\x03\x12\x03\x08\
+    \x18\x19\n\x0b\n\x04\x04\0\x02\x06\x12\x03\t\x02\x1c\n\x0c\n\x05\x04\0\
+    \x02\x06\x04\x12\x03\t\x02\n\n\x0c\n\x05\x04\0\x02\x06\x05\x12\x03\t\x0b\
+    \x11\n\x0c\n\x05\x04\0\x02\x06\x01\x12\x03\t\x12\x17\n\x0c\n\x05\x04\0\
+    \x02\x06\x03\x12\x03\t\x1a\x1b\n\n\n\x02\x04\x01\x12\x04\x0c\0\x0e\x01\n\
+    \n\n\x03\x04\x01\x01\x12\x03\x0c\x08\x12\n\x0b\n\x04\x04\x01\x02\0\x12\
+    \x03\r\x02\x1a\n\x0c\n\x05\x04\x01\x02\0\x04\x12\x03\r\x02\n\n\x0c\n\x05\
+    \x04\x01\x02\0\x05\x12\x03\r\x0b\x10\n\x0c\n\x05\x04\x01\x02\0\x01\x12\
+    \x03\r\x11\x15\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\r\x18\x19\n\n\n\x02\
+    \x04\x02\x12\x04\x10\0\x14\x01\n\n\n\x03\x04\x02\x01\x12\x03\x10\x08\x10\
+    \n\x0b\n\x04\x04\x02\x02\0\x12\x03\x11\x02\x1f\n\x0c\n\x05\x04\x02\x02\0\
+    \x04\x12\x03\x11\x02\n\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03\x11\x0b\x10\
+    \n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x11\x11\x1a\n\x0c\n\x05\x04\x02\
+    \x02\0\x03\x12\x03\x11\x1d\x1e\n\x0b\n\x04\x04\x02\x02\x01\x12\x03\x12\
+    \x02\x1d\n\x0c\n\x05\x04\x02\x02\x01\x04\x12\x03\x12\x02\n\n\x0c\n\x05\
+    \x04\x02\x02\x01\x05\x12\x03\x12\x0b\x11\n\x0c\n\x05\x04\x02\x02\x01\x01\
+    \x12\x03\x12\x12\x18\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\x12\x1b\x1c\
+    \n\x0b\n\x04\x04\x02\x02\x02\x12\x03\x13\x02\x1d\n\x0c\n\x05\x04\x02\x02\
+    \x02\x04\x12\x03\x13\x02\n\n\x0c\n\x05\x04\x02\x02\x02\x05\x12\x03\x13\
+    \x0b\x11\n\x0c\n\x05\x04\x02\x02\x02\x01\x12\x03\x13\x12\x18\n\x0c\n\x05\
+    \x04\x02\x02\x02\x03\x12\x03\x13\x1b\x1c\n\n\n\x02\x04\x03\x12\x04\x16\0\
+    +\x01\n\n\n\x03\x04\x03\x01\x12\x03\x16\x08\r\n\x0c\n\x04\x04\x03\x04\0\
+    \x12\x04\x17\x02\x1b\x03\n\x0c\n\x05\x04\x03\x04\0\x01\x12\x03\x17\x07\
+    \x0f\n\r\n\x06\x04\x03\x04\0\x02\0\x12\x03\x18\x04\x14\n\x0e\n\x07\x04\
+    \x03\x04\0\x02\0\x01\x12\x03\x18\x04\x0f\n\x0e\n\x07\x04\x03\x04\0\x02\0\
+    \x02\x12\x03\x18\x12\x13\n\r\n\x06\x04\x03\x04\0\x02\x01\x12\x03\x19\x04\
+    \x14\n\x0e\n\x07\x04\x03\x04\0\x02\x01\x01\x12\x03\x19\x04\x0f\n\x0e\n\
+    \x07\x04\x03\x04\0\x02\x01\x02\x12\x03\x19\x12\x13\n\r\n\x06\x04\x03\x04\
+    \0\x02\x02\x12\x03\x1a\x04\x11\n\x0e\n\x07\x04\x03\x04\0\x02\x02\x01\x12\
+    \x03\x1a\x04\x0c\n\x0e\n\x07\x04\x03\x04\0\x02\x02\x02\x12\x03\x1a\x0f\
+    \x10\n\x0c\n\x04\x04\x03\x03\0\x12\x04\x1d\x02\"\x03\n\x0c\n\x05\x04\x03\
+    \x03\0\x01\x12\x03\x1d\n\x0e\n\r\n\x06\x04\x03\x03\0\x02\0\x12\x03\x1e\
+    \x04\x1f\n\x0e\n\x07\x04\x03\x03\0\x02\0\x04\x12\x03\x1e\x04\x0c\n\x0e\n\
+    \x07\x04\x03\x03\0\x02\0\x06\x12\x03\x1e\r\x15\n\x0e\n\x07\x04\x03\x03\0\
+    \x02\0\x01\x12\x03\x1e\x16\x1a\n\x0e\n\x07\x04\x03\x03\0\x02\0\x03\x12\
+    \x03\x1e\x1d\x1e\n\r\n\x06\x04\x03\x03\0\x02\x01\x12\x03\x1f\x04)\n\x0e\
+    \n\x07\x04\x03\x03\0\x02\x01\x04\x12\x03\x1f\x04\x0c\n\x0e\n\x07\x04\x03\
+    \x03\0\x02\x01\x06\x12\x03\x1f\r\x18\n\x0e\n\x07\x04\x03\x03\0\x02\x01\
+    \x01\x12\x03\x1f\x19$\n\x0e\n\x07\x04\x03\x03\0\x02\x01\x03\x12\x03\x1f'\
+    (\n\r\n\x06\x04\x03\x03\0\x02\x02\x12\x03\x20\x04'\n\x0e\n\x07\x04\x03\
+    \x03\0\x02\x02\x04\x12\x03\x20\x04\x0c\n\x0e\n\x07\x04\x03\x03\0\x02\x02\
+    \x06\x12\x03\x20\r\x17\n\x0e\n\x07\x04\x03\x03\0\x02\x02\x01\x12\x03\x20\
+    \x18\"\n\x0e\n\x07\x04\x03\x03\0\x02\x02\x03\x12\x03\x20%&\n\r\n\x06\x04\
+    \x03\x03\0\x02\x03\x12\x03!\x04#\n\x0e\n\x07\x04\x03\x03\0\x02\x03\x04\
+    \x12\x03!\x04\x0c\n\x0e\n\x07\x04\x03\x03\0\x02\x03\x06\x12\x03!\r\x15\n\
+    \x0e\n\x07\x04\x03\x03\0\x02\x03\x01\x12\x03!\x16\x1e\n\x0e\n\x07\x04\
+    \x03\x03\0\x02\x03\x03\x12\x03!!\"\n\x0b\n\x04\x04\x03\x02\0\x12\x03$\
+    \x02\x19\n\x0c\n\x05\x04\x03\x02\0\x04\x12\x03$\x02\n\n\x0c\n\x05\x04\
+    \x03\x02\0\x05\x12\x03$\x0b\x11\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03$\
+    \x12\x14\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03$\x17\x18\n\x0b\n\x04\x04\
+    \x03\x02\x01\x12\x03%\x02\x1f\n\x0c\n\x05\x04\x03\x02\x01\x04\x12\x03%\
+    \x02\n\n\x0c\n\x05\x04\x03\x02\x01\x05\x12\x03%\x0b\x10\n\x0c\n\x05\x04\
+    \x03\x02\x01\x01\x12\x03%\x11\x1a\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\
+    \x03%\x1d\x1e\n\x0b\n\x04\x04\x03\x02\x02\x12\x03&\x02\x1c\n\x0c\n\x05\
+    \x04\x03\x02\x02\x04\x12\x03&\x02\n\n\x0c\n\x05\x04\x03\x02\x02\x05\x12\
+    \x03&\x0b\x11\n\x0c\n\x05\x04\x03\x02\x02\x01\x12\x03&\x12\x17\n\x0c\n\
+    \x05\x04\x03\x02\x02\x03\x12\x03&\x1a\x1b\n\x0b\n\x04\x04\x03\x02\x03\
+    \x12\x03'\x02%\n\x0c\n\x05\x04\x03\x02\x03\x04\x12\x03'\x02\n\n\x0c\n\
+    \x05\x04\x03\x02\x03\x05\x12\x03'\x0b\x10\n\x0c\n\x05\x04\x03\x02\x03\
+    \x01\x12\x03'\x11\x20\n\x0c\n\x05\x04\x03\x02\x03\x03\x12\x03'#$\n\x0b\n\
+    \x04\x04\x03\x02\x04\x12\x03(\x02\x19\n\x0c\n\x05\x04\x03\x02\x04\x04\
+    \x12\x03(\x02\n\n\x0c\n\x05\x04\x03\x02\x04\x06\x12\x03(\x0b\x0f\n\x0c\n\
+    \x05\x04\x03\x02\x04\x01\x12\x03(\x10\x14\n\x0c\n\x05\x04\x03\x02\x04\
+    \x03\x12\x03(\x17\x18\n\x0b\n\x04\x04\x03\x02\x05\x12\x03)\x02!\n\x0c\n\
+    \x05\x04\x03\x02\x05\x04\x12\x03)\x02\n\n\x0c\n\x05\x04\x03\x02\x05\x05\
+    \x12\x03)\x0b\x11\n\x0c\n\x05\x04\x03\x02\x05\x01\x12\x03)\x12\x1c\n\x0c\
+    \n\x05\x04\x03\x02\x05\x03\x12\x03)\x1f\x20\n\x0b\n\x04\x04\x03\x02\x06\
+    \x12\x03*\x02!\n\x0c\n\x05\x04\x03\x02\x06\x04\x12\x03*\x02\n\n\x0c\n\
+    \x05\x04\x03\x02\x06\x05\x12\x03*\x0b\x10\n\x0c\n\x05\x04\x03\x02\x06\
+    \x01\x12\x03*\x11\x1c\n\x0c\n\x05\x04\x03\x02\x06\x03\x12\x03*\x1f\x20\n\
+    \n\n\x02\x04\x04\x12\x04,\00\x01\n\n\n\x03\x04\x04\x01\x12\x03,\x08\x11\
+    \n\x0b\n\x04\x04\x04\x02\0\x12\x03-\x02\x1e\n\x0c\n\x05\x04\x04\x02\0\
+    \x04\x12\x03-\x02\n\n\x0c\n\x05\x04\x04\x02\0\x05\x12\x03-\x0b\x11\n\x0c\
+    \n\x05\x04\x04\x02\0\x01\x12\x03-\x12\x19\n\x0c\n\x05\x04\x04\x02\0\x03\
+    \x12\x03-\x1c\x1d\n\x0b\n\x04\x04\x04\x02\x01\x12\x03.\x02\x1d\n\x0c\n\
+    \x05\x04\x04\x02\x01\x04\x12\x03.\x02\n\n\x0c\n\x05\x04\x04\x02\x01\x05\
+    \x12\x03.\x0b\x11\n\x0c\n\x05\x04\x04\x02\x01\x01\x12\x03.\x12\x18\n\x0c\
+    \n\x05\x04\x04\x02\x01\x03\x12\x03.\x1b\x1c\n\x0b\n\x04\x04\x04\x02\x02\
+    \x12\x03/\x02\x1e\n\x0c\n\x05\x04\x04\x02\x02\x04\x12\x03/\x02\n\n\x0c\n\
+    \x05\x04\x04\x02\x02\x05\x12\x03/\x0b\x10\n\x0c\n\x05\x04\x04\x02\x02\
+    \x01\x12\x03/\x11\x19\n\x0c\n\x05\x04\x04\x02\x02\x03\x12\x03/\x1c\x1d\n\
+    \n\n\x02\x04\x05\x12\x042\04\x01\n\n\n\x03\x04\x05\x01\x12\x032\x08\x10\
+    \n\x0b\n\x04\x04\x05\x02\0\x12\x033\x02\x1a\n\x0c\n\x05\x04\x05\x02\0\
+    \x04\x12\x033\x02\n\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x033\x0b\x10\n\x0c\
+    \n\x05\x04\x05\x02\0\x01\x12\x033\x11\x15\n\x0c\n\x05\x04\x05\x02\0\x03\
+    \x12\x033\x18\x19\n\n\n\x02\x04\x06\x12\x046\0=\x01\n\n\n\x03\x04\x06\
+    \x01\x12\x036\x08\x13\n\x0b\n\x04\x04\x06\x02\0\x12\x037\x02\x19\n\x0c\n\
+    \x05\x04\x06\x02\0\x04\x12\x037\x02\n\n\x0c\n\x05\x04\x06\x02\0\x05\x12\
+    \x037\x0b\x11\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x037\x12\x14\n\x0c\n\x05\
+    \x04\x06\x02\0\x03\x12\x037\x17\x18\n\x0b\n\x04\x04\x06\x02\x01\x12\x038\
+    \x02\x1f\n\x0c\n\x05\x04\x06\x02\x01\x04\x12\x038\x02\n\n\x0c\n\x05\x04\
+    \x06\x02\x01\x05\x12\x038\x0b\x10\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\
+    \x038\x11\x1a\n\x0c\n\x05\x04\x06\x02\x01\x03\x12\x038\x1d\x1e\n\x0b\n\
+    \x04\x04\x06\x02\x02\x12\x039\x02\x1c\n\x0c\n\x05\x04\x06\x02\x02\x04\
+    \x12\x039\x02\n\n\x0c\n\x05\x04\x06\x02\x02\x05\x12\x039\x0b\x11\n\x0c\n\
+    \x05\x04\x06\x02\x02\x01\x12\x039\x12\x17\n\x0c\n\x05\x04\x06\x02\x02\
+    \x03\x12\x039\x1a\x1b\n\x0b\n\x04\x04\x06\x02\x03\x12\x03:\x02%\n\x0c\n\
+    \x05\x04\x06\x02\x03\x04\x12\x03:\x02\n\n\x0c\n\x05\x04\x06\x02\x03\x05\
+    \x12\x03:\x0b\x10\n\x0c\n\x05\x04\x06\x02\x03\x01\x12\x03:\x11\x20\n\x0c\
+    \n\x05\x04\x06\x02\x03\x03\x12\x03:#$\n\x0b\n\x04\x04\x06\x02\x04\x12\
+    \x03;\x02!\n\x0c\n\x05\x04\x06\x02\x04\x04\x12\x03;\x02\n\n\x0c\n\x05\
+    \x04\x06\x02\x04\x05\x12\x03;\x0b\x11\n\x0c\n\x05\x04\x06\x02\x04\x01\
+    \x12\x03;\x12\x1c\n\x0c\n\x05\x04\x06\x02\x04\x03\x12\x03;\x1f\x20\n\x0b\
+    \n\x04\x04\x06\x02\x05\x12\x03<\x02!\n\x0c\n\x05\x04\x06\x02\x05\x04\x12\
+    \x03<\x02\n\n\x0c\n\x05\x04\x06\x02\x05\x05\x12\x03<\x0b\x10\n\x0c\n\x05\
+    \x04\x06\x02\x05\x01\x12\x03<\x11\x1c\n\x0c\n\x05\x04\x06\x02\x05\x03\
+    \x12\x03<\x1f\x20\n\n\n\x02\x04\x07\x12\x04?\0A\x01\n\n\n\x03\x04\x07\
+    \x01\x12\x03?\x08\x12\n\x0b\n\x04\x04\x07\x02\0\x12\x03@\x02\x1d\n\x0c\n\
+    \x05\x04\x07\x02\0\x04\x12\x03@\x02\n\n\x0c\n\x05\x04\x07\x02\0\x05\x12\
+    \x03@\x0b\x10\n\x0c\n\x05\x04\x07\x02\0\x01\x12\x03@\x11\x18\n\x0c\n\x05\
+    \x04\x07\x02\0\x03\x12\x03@\x1b\x1c\n\n\n\x02\x04\x08\x12\x04C\0E\x01\n\
+    \n\n\x03\x04\x08\x01\x12\x03C\x08\x0f\n\x0b\n\x04\x04\x08\x02\0\x12\x03D\
+    \x02#\n\x0c\n\x05\x04\x08\x02\0\x04\x12\x03D\x02\n\n\x0c\n\x05\x04\x08\
+    \x02\0\x06\x12\x03D\x0b\x16\n\x0c\n\x05\x04\x08\x02\0\x01\x12\x03D\x17\
+    \x1e\n\x0c\n\x05\x04\x08\x02\0\x03\x12\x03D!\"\n\n\n\x02\x04\t\x12\x04G\
+    \0X\x01\n\n\n\x03\x04\t\x01\x12\x03G\x08\x10\n\x0c\n\x04\x04\t\x04\0\x12\
+    \x04H\x02O\x03\n\x0c\n\x05\x04\t\x04\0\x01\x12\x03H\x07\x12\n\r\n\x06\
+    \x04\t\x04\0\x02\0\x12\x03I\x04\x12\n\x0e\n\x07\x04\t\x04\0\x02\0\x01\
+    \x12\x03I\x04\r\n\x0e\n\x07\x04\t\x04\0\x02\0\x02\x12\x03I\x10\x11\n\r\n\
+    \x06\x04\t\x04\0\x02\x01\x12\x03J\x04\x0e\n\x0e\n\x07\x04\t\x04\0\x02\
+    \x01\x01\x12\x03J\x04\t\n\x0e\n\x07\x04\t\x04\0\x02\x01\x02\x12\x03J\x0c\
+    \r\n\r\n\x06\x04\t\x04\0\x02\x02\x12\x03K\x04\x14\n\x0e\n\x07\x04\t\x04\
+    \0\x02\x02\x01\x12\x03K\x04\x0f\n\x0e\n\x07\x04\t\x04\0\x02\x02\x02\x12\
+    \x03K\x12\x13\n\r\n\x06\x04\t\x04\0\x02\x03\x12\x03L\x04\x12\n\x0e\n\x07\
+    \x04\t\x04\0\x02\x03\x01\x12\x03L\x04\r\n\x0e\n\x07\x04\t\x04\0\x02\x03\
+    \x02\x12\x03L\x10\x11\n\r\n\x06\x04\t\x04\0\x02\x04\x12\x03M\x04\x14\n\
+    \x0e\n\x07\x04\t\x04\0\x02\x04\x01\x12\x03M\x04\x0f\n\x0e\n\x07\x04\t\
+    \x04\0\x02\x04\x02\x12\x03M\x12\x13\n\r\n\x06\x04\t\x04\0\x02\x05\x12\
+    \x03N\x04\x10\n\x0e\n\x07\x04\t\x04\0\x02\x05\x01\x12\x03N\x04\x0b\n\x0e\
+    \n\x07\x04\t\x04\0\x02\x05\x02\x12\x03N\x0e\x0f\n\x0b\n\x04\x04\t\x02\0\
+    \x12\x03Q\x02\x20\n\x0c\n\x05\x04\t\x02\0\x04\x12\x03Q\x02\n\n\x0c\n\x05\
+    \x04\t\x02\0\x06\x12\x03Q\x0b\x16\n\x0c\n\x05\x04\t\x02\0\x01\x12\x03Q\
+    \x17\x1b\n\x0c\n\x05\x04\t\x02\0\x03\x12\x03Q\x1e\x1f\n\x0b\n\x04\x04\t\
+    \x02\x01\x12\x03R\x02#\n\x0c\n\x05\x04\t\x02\x01\x04\x12\x03R\x02\n\n\
+    \x0c\n\x05\x04\t\x02\x01\x06\x12\x03R\x0b\x14\n\x0c\n\x05\x04\t\x02\x01\
+    \x01\x12\x03R\x15\x1e\n\x0c\n\x05\x04\t\x02\x01\x03\x12\x03R!\"\n\x0b\n\
+    \x04\x04\t\x02\x02\x12\x03S\x02\x1b\n\x0c\n\x05\x04\t\x02\x02\x04\x12\
+    \x03S\x02\n\n\x0c\n\x05\x04\t\x02\x02\x06\x12\x03S\x0b\x10\n\x0c\n\x05\
+    \x04\t\x02\x02\x01\x12\x03S\x11\x16\n\x0c\n\x05\x04\t\x02\x02\x03\x12\
+    \x03S\x19\x1a\n\x0b\n\x04\x04\t\x02\x03\x12\x03T\x02'\n\x0c\n\x05\x04\t\
+    \x02\x03\x04\x12\x03T\x02\n\n\x0c\n\x05\x04\t\x02\x03\x06\x12\x03T\x0b\
+    \x16\n\x0c\n\x05\x04\t\x02\x03\x01\x12\x03T\x17\"\n\x0c\n\x05\x04\t\x02\
+    \x03\x03\x12\x03T%&\n\x0b\n\x04\x04\t\x02\x04\x12\x03U\x02!\n\x0c\n\x05\
+    \x04\t\x02\x04\x04\x12\x03U\x02\n\n\x0c\n\x05\x04\t\x02\x04\x06\x12\x03U\
+    \x0b\x13\n\x0c\n\x05\x04\t\x02\x04\x01\x12\x03U\x14\x1c\n\x0c\n\x05\x04\
+    \t\x02\x04\x03\x12\x03U\x1f\x20\n\x0b\n\x04\x04\t\x02\x05\x12\x03V\x02%\
+    \n\x0c\n\x05\x04\t\x02\x05\x04\x12\x03V\x02\n\n\x0c\n\x05\x04\t\x02\x05\
+    \x06\x12\x03V\x0b\x15\n\x0c\n\x05\x04\t\x02\x05\x01\x12\x03V\x16\x20\n\
+    \x0c\n\x05\x04\t\x02\x05\x03\x12\x03V#$\n\x0b\n\x04\x04\t\x02\x06\x12\
+    \x03W\x02\x1f\n\x0c\n\x05\x04\t\x02\x06\x04\x12\x03W\x02\n\n\x0c\n\x05\
+    \x04\t\x02\x06\x06\x12\x03W\x0b\x12\n\x0c\n\x05\x04\t\x02\x06\x01\x12\
+    \x03W\x13\x1a\n\x0c\n\x05\x04\t\x02\x06\x03\x12\x03W\x1d\x1e\n\n\n\x02\
+    \x04\n\x12\x04Z\0\\\x01\n\n\n\x03\x04\n\x01\x12\x03Z\x08\x0f\n\x0b\n\x04\
+    \x04\n\x02\0\x12\x03[\x02\x1f\n\x0c\n\x05\x04\n\x02\0\x04\x12\x03[\x02\n\
+    \n\x0c\n\x05\x04\n\x02\0\x06\x12\x03[\x0b\x15\n\x0c\n\x05\x04\n\x02\0\
+    \x01\x12\x03[\x16\x1a\n\x0c\n\x05\x04\n\x02\0\x03\x12\x03[\x1d\x1e\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -0,0 +1,321 @@
+use hex;
+use protobuf::Message;
+use serde_json;
+use serde_json::Value;
+use warp::http::{Response, StatusCode};
+use mempool;
+use {Block, BlockData, Blockchain, Context, Sha256Hash, Transaction, address_from_public_key, block_to_pb,
+     sha256hash_from_slice};
+
+/// Largest request body accepted, batches included
+pub const MAX_REQUEST_SIZE: u64 = 1024 * 1024;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Codes Bitcoin Core uses for the same failures
+const NOT_FOUND: i64 = -5;
+const TRANSACTION_REJECTED: i64 = -26;
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new<T: Into<String>>(code: i64, message: T) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": error.code,
+            "message": error.message,
+        },
+        "id": id,
+    })
+}
+
+/// Answers a JSON-RPC 2.0 request body, which may hold a single request or a batch. Notifications get no
+/// response, so a body of only notifications is answered with an empty 204.
+pub fn reply(context: &Context, body: &[u8]) -> Response<String> {
+    let response = match serde_json::from_slice(body) {
+        Ok(Value::Array(requests)) => {
+            if requests.is_empty() {
+                Some(error_response(Value::Null, RpcError::new(INVALID_REQUEST, "Empty batch")))
+            } else {
+                let responses: Vec<Value> = requests.into_iter()
+                    .filter_map(|request| handle_request(context, request))
+                    .collect();
+                if responses.is_empty() {
+                    None
+                } else {
+                    Some(Value::Array(responses))
+                }
+            }
+        }
+        Ok(request) => handle_request(context, request),
+        Err(e) => Some(error_response(Value::Null, RpcError::new(PARSE_ERROR, format!("Parse error: {}", e))))
+    };
+
+    let mut builder = Response::builder();
+    let reply = match response {
+        Some(response) => builder.header("content-type", "application/json").body(response.to_string()),
+        None => builder.status(StatusCode::NO_CONTENT).body(String::new())
+    };
+    reply.expect("Failed to build JSON-RPC response")
+}
+
+fn handle_request(context: &Context, request: Value) -> Option<Value> {
+    let mut request = match request {
+        Value::Object(request) => request,
+        _ => return Some(error_response(Value::Null, RpcError::new(INVALID_REQUEST, "Request must be an object")))
+    };
+    let id = match request.remove("id") {
+        Some(id @ Value::Null) | Some(id @ Value::Number(_)) | Some(id @ Value::String(_)) => Some(id),
+        Some(_) => {
+            return Some(error_response(Value::Null, RpcError::new(INVALID_REQUEST, "Id must be a string or number")));
+        }
+        None => None
+    };
+    let response_id = id.clone().unwrap_or(Value::Null);
+    if request.get("jsonrpc") != Some(&Value::String("2.0".to_string())) {
+        return Some(error_response(response_id, RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\"")));
+    }
+    let method = match request.remove("method") {
+        Some(Value::String(method)) => method,
+        _ => return Some(error_response(response_id, RpcError::new(INVALID_REQUEST, "Method must be a string")))
+    };
+    let params = match request.remove("params") {
+        Some(Value::Array(params)) => params,
+        None => vec![],
+        Some(_) => {
+            return Some(error_response(response_id, RpcError::new(INVALID_PARAMS, "Params must be an array")));
+        }
+    };
+
+    let result = call(context, &method, &params);
+    // A request without an id is a notification and gets no response, even if it fails
+    match id {
+        Some(id) => Some(match result {
+            Ok(result) => json!({
+                "jsonrpc": "2.0",
+                "result": result,
+                "id": id,
+            }),
+            Err(e) => error_response(id, e),
+        }),
+        None => None
+    }
+}
+
+fn call(context: &Context, method: &str, params: &[Value]) -> Result<Value, RpcError> {
+    match method {
+        "getblockcount" => get_block_count(context),
+        "getbestblockhash" => get_best_block_hash(context),
+        "getblockhash" => get_block_hash(context, params),
+        "getblock" => get_block(context, params),
+        "getbalance" => get_balance(context, params),
+        "getmempoolinfo" => get_mempool_info(context),
+        "sendtransaction" => send_transaction(context, params),
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method not found: {}", method)))
+    }
+}
+
+fn param<'a>(params: &'a [Value], index: usize, name: &str) -> Result<&'a Value, RpcError> {
+    match params.get(index) {
+        Some(value) => Ok(value),
+        None => Err(RpcError::new(INVALID_PARAMS, format!("Missing {} parameter", name)))
+    }
+}
+
+fn field<'a>(fields: &'a serde_json::Map<String, Value>, name: &str) -> Result<&'a Value, RpcError> {
+    match fields.get(name) {
+        Some(value) => Ok(value),
+        None => Err(RpcError::new(INVALID_PARAMS, format!("Missing {} field", name)))
+    }
+}
+
+fn as_u64(value: &Value, name: &str) -> Result<u64, RpcError> {
+    match value.as_u64() {
+        Some(value) => Ok(value),
+        None => Err(RpcError::new(INVALID_PARAMS, format!("{} must be a non-negative integer", name)))
+    }
+}
+
+fn as_hex(value: &Value, name: &str, len: usize) -> Result<Vec<u8>, RpcError> {
+    match value.as_str().map(hex::decode) {
+        Some(Ok(bytes)) => {
+            if bytes.len() == len {
+                return Ok(bytes);
+            }
+        }
+        _ => {}
+    }
+    Err(RpcError::new(INVALID_PARAMS, format!("{} must be {} hex encoded bytes", name, len)))
+}
+
+fn as_hash(value: &Value, name: &str) -> Result<Sha256Hash, RpcError> {
+    Ok(sha256hash_from_slice(&as_hex(value, name, 32)?))
+}
+
+fn tip(chain: &Blockchain) -> Result<&Block, RpcError> {
+    match chain.tip() {
+        Some(block) => Ok(block),
+        None => Err(RpcError::new(NOT_FOUND, "Chain has no blocks"))
+    }
+}
+
+fn get_block_count(context: &Context) -> Result<Value, RpcError> {
+    let chain = context.blockchain.read().unwrap();
+    let height = tip(&chain)?.inner.read().unwrap().id;
+    Ok(json!(height))
+}
+
+fn get_best_block_hash(context: &Context) -> Result<Value, RpcError> {
+    let chain = context.blockchain.read().unwrap();
+    Ok(json!(hex::encode_upper(&tip(&chain)?.hash())))
+}
+
+fn get_block_hash(context: &Context, params: &[Value]) -> Result<Value, RpcError> {
+    let height = as_u64(param(params, 0, "height")?, "height")?;
+    let chain = context.blockchain.read().unwrap();
+    match chain.main_chain_block(height as usize) {
+        Some(block) => Ok(json!(hex::encode_upper(&block.hash()))),
+        None => Err(RpcError::new(NOT_FOUND, "Block height out of range"))
+    }
+}
+
+fn data_json(data: &Box<BlockData>) -> Value {
+    match data.as_transaction() {
+        Some(transaction) => return transaction_json(transaction),
+        None => {}
+    }
+    match data.as_coinbase() {
+        Some(coinbase) => {
+            return json!({
+                "type": "coinbase",
+                "recipient": hex::encode_upper(&coinbase.recipient),
+                "amount": coinbase.amount,
+                "height": coinbase.height,
+            });
+        }
+        None => {}
+    }
+    match data.as_binary_data() {
+        Some(binary_data) => {
+            json!({
+                "type": "binary",
+                "data": hex::encode_upper(&binary_data.data),
+            })
+        }
+        None => Value::Null
+    }
+}
+
+fn transaction_json(transaction: &Transaction) -> Value {
+    json!({
+        "type": "transaction",
+        "id": hex::encode_upper(&transaction.hash()),
+        "sender": hex::encode_upper(&transaction.sender),
+        "recipient": hex::encode_upper(&transaction.recipient),
+        "amount": transaction.amount,
+        "fee": transaction.fee,
+        "nonce": transaction.nonce,
+        "public_key": hex::encode_upper(&transaction.public_key),
+        "signature": hex::encode_upper(&transaction.signature),
+    })
+}
+
+/// The block as JSON, or if `verbose` is false its protobuf encoding in hex
+fn get_block(context: &Context, params: &[Value]) -> Result<Value, RpcError> {
+    let hash = as_hash(param(params, 0, "blockhash")?, "blockhash")?;
+    let verbose = match params.get(1) {
+        Some(verbose) => match verbose.as_bool() {
+            Some(verbose) => verbose,
+            None => return Err(RpcError::new(INVALID_PARAMS, "verbose must be a boolean"))
+        },
+        None => true
+    };
+    let chain = context.blockchain.read().unwrap();
+    let block = match chain.block_by_hash(&hash) {
+        Some(block) => block,
+        None => return Err(RpcError::new(NOT_FOUND, "Block not found"))
+    };
+    if !verbose {
+        let bytes = block_to_pb(block).write_to_bytes().expect("Failed to encode block");
+        return Ok(json!(hex::encode_upper(&bytes)));
+    }
+
+    let inner = block.inner.read().unwrap();
+    let previous_block_hash = if inner.prev_block_index < 0 {
+        Value::Null
+    } else {
+        json!(hex::encode_upper(&inner.prev_block_hash))
+    };
+    let next_block_hashes: Vec<String> = inner.next_block_indexes.iter()
+        .map(|index| hex::encode_upper(&chain.blocks[*index as usize].hash()))
+        .collect();
+    let data: Vec<Value> = inner.data.iter().map(data_json).collect();
+    Ok(json!({
+        "hash": hex::encode_upper(&inner.hash()),
+        "height": inner.id,
+        "confirmations": chain.confirmations(block),
+        "time": inner.timestamp,
+        "nonce": inner.nonce,
+        "difficulty": inner.difficulty,
+        "merkleroot": hex::encode_upper(&inner.merkle_root),
+        "previousblockhash": previous_block_hash,
+        "nextblockhashes": next_block_hashes,
+        "data": data,
+    }))
+}
+
+fn get_balance(context: &Context, params: &[Value]) -> Result<Value, RpcError> {
+    let address = as_hash(param(params, 0, "address")?, "address")?;
+    let chain = context.blockchain.read().unwrap();
+    Ok(json!(chain.ledger.balance(&address)))
+}
+
+fn get_mempool_info(context: &Context) -> Result<Value, RpcError> {
+    let chain = context.blockchain.read().unwrap();
+    Ok(json!({
+        "size": chain.mempool.len(),
+        "bytes": chain.mempool.size(),
+        "maxbytes": mempool::MAX_MEMPOOL_SIZE,
+        "maxtransactions": mempool::MAX_MEMPOOL_TRANSACTIONS,
+    }))
+}
+
+/// Takes a single object with the same fields as the publishTransaction mutation, returning the transaction id
+fn send_transaction(context: &Context, params: &[Value]) -> Result<Value, RpcError> {
+    let fields = match param(params, 0, "transaction")?.as_object() {
+        Some(fields) => fields,
+        None => return Err(RpcError::new(INVALID_PARAMS, "transaction must be an object"))
+    };
+    let public_key = as_hash(field(fields, "public_key")?, "public_key")?;
+    let transaction = Transaction {
+        sender: address_from_public_key(&public_key),
+        recipient: as_hash(field(fields, "to")?, "to")?,
+        amount: as_u64(field(fields, "amount")?, "amount")?,
+        fee: as_u64(field(fields, "fee")?, "fee")?,
+        nonce: as_u64(field(fields, "nonce")?, "nonce")?,
+        public_key,
+        signature: as_hex(field(fields, "signature")?, "signature", 64)?,
+    };
+
+    let hash = match context.blockchain.write().unwrap().add_transaction(transaction.clone()) {
+        Ok(hash) => hash,
+        Err(e) => return Err(RpcError::new(TRANSACTION_REJECTED, format!("Transaction rejected: {}", e)))
+    };
+    context.network.announce_transaction(&transaction);
+    Ok(json!(hex::encode_upper(&hash)))
+}
@@ -0,0 +1,21 @@
+use juniper::{InputValue, Value};
+
+/// A 64 bit unsigned integer, which doesn't fit in GraphQL's 32 bit `Int`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct U64(pub u64);
+
+graphql_scalar!(U64 {
+    description: "64 bit unsigned integer, returned as a decimal string and accepted as a string or non-negative Int"
+
+    resolve(&self) -> Value {
+        Value::string(&self.0.to_string())
+    }
+
+    from_input_value(v: &InputValue) -> Option<U64> {
+        match *v {
+            InputValue::Int(i) if i >= 0 => Some(U64(i as u64)),
+            InputValue::String(ref s) => s.parse().ok().map(U64),
+            _ => None
+        }
+    }
+});
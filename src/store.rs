@@ -0,0 +1,739 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashMap};
+use crypto::sha2::Sha256;
+use crypto::digest::Digest;
+use hex;
+use protobuf::{self, Message};
+use proto::chain;
+use p2p;
+use {Block, Sha256Hash, Transaction, block_to_pb, pb_to_block, transaction_to_pb, pb_to_transaction,
+     sha256hash_from_slice};
+
+/// A new segment file is started once the current one would grow past this
+const MAX_SEGMENT_SIZE: u64 = 128 * 1024 * 1024;
+const INDEX_FILE: &str = "index.dat";
+/// Transactions that haven't been mined yet
+const MEMPOOL_FILE: &str = "mempool.dat";
+/// Corrupt block data is moved here rather than deleted
+const QUARANTINE_DIR: &str = "quarantine";
+/// Block hash, height, segment number, offset, length and checksum
+const INDEX_ENTRY_SIZE: usize = 32 + 8 + 4 + 8 + 4 + 4;
+/// Each block in a segment is preceded by its length and checksum
+const RECORD_HEADER_SIZE: u64 = 8;
+
+#[derive(Debug, Clone, Copy)]
+struct Location {
+    height: u64,
+    segment: u32,
+    /// Where the block's record starts in the segment
+    offset: u64,
+    length: u32,
+}
+
+impl Location {
+    fn end(&self) -> u64 {
+        self.offset + RECORD_HEADER_SIZE + self.length as u64
+    }
+}
+
+/// What was found reading a segment record by record
+struct Scan {
+    blocks: Vec<(Sha256Hash, Location)>,
+    /// Offsets and contents of corrupt stretches that have a valid record after them
+    corrupt: Vec<(u64, Vec<u8>)>,
+    /// End of the last valid record. Anything after it is either a block cut off part way through being written or
+    /// corrupt data with nothing valid after it.
+    end: u64,
+}
+
+fn checksum(bytes: &[u8]) -> [u8; 4] {
+    let mut hasher = Sha256::new();
+    let mut hash = Sha256Hash::default();
+    hasher.input(bytes);
+    hasher.result(&mut hash);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn segment_path(dir: &Path, segment: u32) -> PathBuf {
+    dir.join(format!("blk{:05}.dat", segment))
+}
+
+fn open_segment(dir: &Path, segment: u32) -> io::Result<File> {
+    OpenOptions::new().read(true).append(true).create(true).open(segment_path(dir, segment))
+}
+
+/// Number of the newest segment file in the directory
+fn last_segment(dir: &Path) -> io::Result<u32> {
+    let mut last = 0;
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        match name.to_str() {
+            Some(name) if name.len() == 12 && name.starts_with("blk") && name.ends_with(".dat") => {
+                match name[3..8].parse() {
+                    Ok(segment) => last = last.max(segment),
+                    Err(_) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(last)
+}
+
+/// Makes newly created or renamed files in the directory survive a crash
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Writes the file under a temporary name and renames it into place, so after a crash it's either the old or the
+/// new contents and never a mix of the two
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    {
+        let mut tmp = File::create(&tmp_path)?;
+        tmp.write_all(bytes)?;
+        tmp.sync_all()?;
+    }
+    fs::rename(&tmp_path, path)?;
+    sync_dir(path.parent().unwrap_or(Path::new(".")))
+}
+
+fn be_bytes(val: u64, len: usize) -> Vec<u8> {
+    (0..len).rev().map(|i| (val >> (i * 8)) as u8).collect()
+}
+
+fn from_be_bytes(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |val, byte| (val << 8) | *byte as u64)
+}
+
+fn encode_entry(hash: &Sha256Hash, location: &Location) -> Vec<u8> {
+    let mut entry = Vec::with_capacity(INDEX_ENTRY_SIZE);
+    entry.extend(hash.iter());
+    entry.extend(be_bytes(location.height, 8));
+    entry.extend(be_bytes(location.segment as u64, 4));
+    entry.extend(be_bytes(location.offset, 8));
+    entry.extend(be_bytes(location.length as u64, 4));
+    let sum = checksum(&entry);
+    entry.extend(sum.iter());
+    entry
+}
+
+fn decode_entry(entry: &[u8]) -> Option<(Sha256Hash, Location)> {
+    if entry[56..60] != checksum(&entry[..56]) {
+        return None;
+    }
+    Some((sha256hash_from_slice(&entry[0..32]), Location {
+        height: from_be_bytes(&entry[32..40]),
+        segment: from_be_bytes(&entry[40..44]) as u32,
+        offset: from_be_bytes(&entry[44..52]),
+        length: from_be_bytes(&entry[52..56]) as u32,
+    }))
+}
+
+fn encode_record(payload: &[u8]) -> Vec<u8> {
+    let mut record = be_bytes(payload.len() as u64, 4);
+    record.extend(checksum(payload).iter());
+    record.extend(payload);
+    record
+}
+
+/// The block in a whole record, if its checksum matches and it's well formed
+fn decode_record(record: &[u8]) -> Option<Block> {
+    let payload = &record[RECORD_HEADER_SIZE as usize..];
+    if record[4..8] != checksum(payload) {
+        return None;
+    }
+    match protobuf::parse_from_bytes::<chain::Block>(payload) {
+        Ok(ref msg) if p2p::well_formed_block(msg) => Some(pb_to_block(msg)),
+        _ => None
+    }
+}
+
+/// The block in the record starting at `pos` and where the record ends, if the record is whole and valid. No valid
+/// block is bigger than a peer may send, so a longer length is corrupt and isn't checksummed.
+fn record_at(bytes: &[u8], pos: usize) -> Option<(Block, usize)> {
+    let length = from_be_bytes(&bytes[pos..pos + 4]) as usize;
+    if length > p2p::MAX_MESSAGE_SIZE {
+        return None;
+    }
+    let record_end = pos + RECORD_HEADER_SIZE as usize + length;
+    if record_end > bytes.len() {
+        return None;
+    }
+    decode_record(&bytes[pos..record_end]).map(|block| (block, record_end))
+}
+
+/// Reads every valid record in the segment from `from` onwards. A corrupt length can't be trusted to find the next
+/// record, so after anything invalid the scan moves on a byte at a time until a valid record turns up again.
+fn scan_segment(dir: &Path, segment: u32, from: u64) -> io::Result<Scan> {
+    let mut f = File::open(segment_path(dir, segment))?;
+    f.seek(SeekFrom::Start(from))?;
+    let mut bytes = vec![];
+    f.read_to_end(&mut bytes)?;
+
+    let mut scan = Scan { blocks: vec![], corrupt: vec![], end: from };
+    let mut pos = 0;
+    let mut corrupt_from = None;
+    while bytes.len() - pos >= RECORD_HEADER_SIZE as usize {
+        match record_at(&bytes, pos) {
+            Some((block, record_end)) => {
+                match corrupt_from.take() {
+                    Some(start) => scan.corrupt.push((from + start as u64, bytes[start..pos].to_vec())),
+                    None => {}
+                }
+                let height = block.inner.read().unwrap().id;
+                let length = (record_end - pos - RECORD_HEADER_SIZE as usize) as u32;
+                scan.blocks.push((block.hash(), Location { height, segment, offset: from + pos as u64, length }));
+                pos = record_end;
+                scan.end = from + pos as u64;
+            }
+            None => {
+                if corrupt_from.is_none() {
+                    corrupt_from = Some(pos);
+                }
+                pos += 1;
+            }
+        }
+    }
+    Ok(scan)
+}
+
+/// Blocks are appended to numbered segment files as they're accepted and never rewritten. Every record and index
+/// entry carries a checksum. An index entry is only written once its block has been synced to disk, and on open the
+/// index is checked against the segments: whole blocks written after the last entry are indexed, and corrupt data
+/// or a partly written block at the end is moved to the quarantine directory instead of failing the load. Opened
+/// read only, the same checks are made but nothing in the directory is changed.
+#[derive(Debug)]
+pub struct BlockStore {
+    dir: PathBuf,
+    index: File,
+    segment: File,
+    segment_number: u32,
+    segment_len: u64,
+    locations: HashMap<Sha256Hash, Location>,
+    heights: BTreeMap<u64, Vec<Sha256Hash>>,
+    /// Number of corrupt records and files moved out of the way since opening, or found if opened read only
+    quarantined: usize,
+    read_only: bool,
+}
+
+impl BlockStore {
+    pub fn open(dir: &str) -> io::Result<Self> {
+        let dir = PathBuf::from(dir);
+        fs::create_dir_all(&dir)?;
+        let mut store = Self {
+            index: OpenOptions::new().read(true).append(true).create(true).open(dir.join(INDEX_FILE))?,
+            segment: open_segment(&dir, 0)?,
+            dir,
+            segment_number: 0,
+            segment_len: 0,
+            locations: HashMap::new(),
+            heights: BTreeMap::new(),
+            quarantined: 0,
+            read_only: false,
+        };
+        store.recover()?;
+        store.segment = open_segment(&store.dir, store.segment_number)?;
+        store.segment_len = store.segment.metadata()?.len();
+        Ok(store)
+    }
+
+    /// Opens a store that already exists for reading. Corrupt data is counted in `quarantined` but left in place, and
+    /// blocks can't be appended.
+    pub fn open_read_only(dir: &str) -> io::Result<Self> {
+        let dir = PathBuf::from(dir);
+        let mut store = Self {
+            index: File::open(dir.join(INDEX_FILE))?,
+            segment: File::open(segment_path(&dir, 0))?,
+            dir,
+            segment_number: 0,
+            segment_len: 0,
+            locations: HashMap::new(),
+            heights: BTreeMap::new(),
+            quarantined: 0,
+            read_only: true,
+        };
+        store.recover()?;
+        Ok(store)
+    }
+
+    /// Loads the index, rebuilding it from the segments if any entry is damaged, then picks up whatever was written
+    /// to the segments after the last entry
+    fn recover(&mut self) -> io::Result<()> {
+        let mut bytes = vec![];
+        self.index.read_to_end(&mut bytes)?;
+        let mut rewrite_index = bytes.len() % INDEX_ENTRY_SIZE != 0;
+        if rewrite_index {
+            println!("Discarding a partially written block index entry");
+        }
+
+        let entries: Option<Vec<(Sha256Hash, Location)>> = bytes.chunks(INDEX_ENTRY_SIZE)
+            .filter(|entry| entry.len() == INDEX_ENTRY_SIZE)
+            .map(decode_entry)
+            .collect();
+        let (entries, rebuilding) = match entries {
+            Some(ref entries) if self.entries_in_bounds(entries)? => (entries.clone(), false),
+            _ => {
+                println!("Block index is corrupt, rebuilding it from the block files");
+                rewrite_index = true;
+                (vec![], true)
+            }
+        };
+
+        let (mut segment, mut from) = (0, 0);
+        for (hash, location) in entries {
+            if (location.segment, location.end()) > (segment, from) {
+                segment = location.segment;
+                from = location.end();
+            }
+            self.insert(hash, location);
+        }
+
+        let last = last_segment(&self.dir)?;
+        while segment <= last {
+            let path = segment_path(&self.dir, segment);
+            if path.exists() {
+                let scan = scan_segment(&self.dir, segment, from)?;
+                for (hash, location) in scan.blocks {
+                    if !self.locations.contains_key(&hash) {
+                        if !rebuilding {
+                            println!("Recovered unindexed block #{} {}", location.height, hex::encode(hash));
+                        }
+                        self.insert(hash, location);
+                        rewrite_index = true;
+                    }
+                }
+                for (offset, record) in scan.corrupt {
+                    self.quarantine(segment, offset, &record)?;
+                }
+
+                // Whatever follows the last valid record is cut off so it isn't scanned again on the next open, but
+                // it can't be told apart from a block with a damaged length so it's kept in quarantine first
+                let len = fs::metadata(&path)?.len();
+                if len > scan.end {
+                    let mut tail = vec![];
+                    let mut f = File::open(&path)?;
+                    f.seek(SeekFrom::Start(scan.end))?;
+                    f.read_to_end(&mut tail)?;
+                    self.quarantine(segment, scan.end, &tail)?;
+                    if !self.read_only {
+                        let f = OpenOptions::new().write(true).open(&path)?;
+                        f.set_len(scan.end)?;
+                        f.sync_all()?;
+                    }
+                }
+            }
+            segment += 1;
+            from = 0;
+        }
+        self.segment_number = last;
+
+        if rewrite_index {
+            self.rewrite_index()?;
+        }
+        Ok(())
+    }
+
+    /// Whether every entry points inside a segment that exists
+    fn entries_in_bounds(&self, entries: &[(Sha256Hash, Location)]) -> io::Result<bool> {
+        let mut segment_lens = HashMap::new();
+        for &(_, ref location) in entries {
+            if !segment_lens.contains_key(&location.segment) {
+                let len = match fs::metadata(segment_path(&self.dir, location.segment)) {
+                    Ok(metadata) => metadata.len(),
+                    Err(_) => 0
+                };
+                segment_lens.insert(location.segment, len);
+            }
+            if location.end() > segment_lens[&location.segment] {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn insert(&mut self, hash: Sha256Hash, location: Location) {
+        self.heights.entry(location.height).or_insert_with(Vec::new).push(hash);
+        self.locations.insert(hash, location);
+    }
+
+    fn remove(&mut self, hash: &Sha256Hash) {
+        match self.locations.remove(hash) {
+            Some(location) => {
+                match self.heights.get_mut(&location.height) {
+                    Some(hashes) => hashes.retain(|other| other != hash),
+                    None => {}
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Replaces the index with one listing exactly the blocks we know about
+    fn rewrite_index(&mut self) -> io::Result<()> {
+        if self.read_only {
+            return Ok(());
+        }
+        let mut entries: Vec<(&Sha256Hash, &Location)> = self.locations.iter().collect();
+        entries.sort_by_key(|&(_, location)| (location.segment, location.offset));
+        let mut bytes = Vec::with_capacity(entries.len() * INDEX_ENTRY_SIZE);
+        for (hash, location) in entries {
+            bytes.extend(encode_entry(hash, location));
+        }
+        let path = self.dir.join(INDEX_FILE);
+        write_atomic(&path, &bytes)?;
+        self.index = OpenOptions::new().read(true).append(true).open(path)?;
+        Ok(())
+    }
+
+    /// Where to put `file_name` in the quarantine directory. A number is added to the name if it's already taken, as
+    /// the same offset or file can turn out to be corrupt again after being quarantined.
+    fn quarantine_path(&self, file_name: &str) -> io::Result<PathBuf> {
+        let dir = self.dir.join(QUARANTINE_DIR);
+        fs::create_dir_all(&dir)?;
+        let (stem, extension) = match file_name.rfind('.') {
+            Some(i) if i > 0 => file_name.split_at(i),
+            _ => (file_name, "")
+        };
+        let mut path = dir.join(file_name);
+        let mut copy = 1;
+        while path.exists() {
+            path = dir.join(format!("{}-{}{}", stem, copy, extension));
+            copy += 1;
+        }
+        Ok(path)
+    }
+
+    fn quarantine(&mut self, segment: u32, offset: u64, bytes: &[u8]) -> io::Result<()> {
+        if self.read_only {
+            println!("Found {} bytes of corrupt block data in {} at offset {}",
+                     bytes.len(), segment_path(&self.dir, segment).display(), offset);
+            self.quarantined += 1;
+            return Ok(());
+        }
+        let path = self.quarantine_path(&format!("blk{:05}-{}.dat", segment, offset))?;
+        write_atomic(&path, bytes)?;
+        println!("Quarantined {} bytes of corrupt block data from {} at offset {} to {}",
+                 bytes.len(), segment_path(&self.dir, segment).display(), offset, path.display());
+        self.quarantined += 1;
+        Ok(())
+    }
+
+    /// Moves a file that can't be read out of the data directory
+    pub fn quarantine_file(&mut self, path: &Path) -> io::Result<()> {
+        if self.read_only {
+            println!("Found unreadable file {}", path.display());
+            self.quarantined += 1;
+            return Ok(());
+        }
+        let file_name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned();
+        let quarantined_path = self.quarantine_path(&file_name)?;
+        fs::rename(path, &quarantined_path)?;
+        sync_dir(&self.dir.join(QUARANTINE_DIR))?;
+        println!("Quarantined unreadable file {} to {}", path.display(), quarantined_path.display());
+        self.quarantined += 1;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    pub fn quarantined(&self) -> usize {
+        self.quarantined
+    }
+
+    pub fn contains(&self, hash: &Sha256Hash) -> bool {
+        self.locations.contains_key(hash)
+    }
+
+    /// Writes the block to the end of the current segment and indexes it, syncing both before returning. Blocks
+    /// that are already stored are skipped.
+    pub fn append(&mut self, block: &Block) -> io::Result<()> {
+        let hash = block.hash();
+        if self.contains(&hash) {
+            return Ok(());
+        }
+        let payload = block_to_pb(block).write_to_bytes().map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let record = encode_record(&payload);
+
+        if self.segment_len > 0 && self.segment_len + record.len() as u64 > MAX_SEGMENT_SIZE {
+            // A crash may have left a segment behind that no block was indexed in
+            let segment = open_segment(&self.dir, self.segment_number + 1)?;
+            segment.set_len(0)?;
+            sync_dir(&self.dir)?;
+            self.segment = segment;
+            self.segment_number += 1;
+            self.segment_len = 0;
+        }
+
+        let location = Location {
+            height: block.inner.read().unwrap().id,
+            segment: self.segment_number,
+            offset: self.segment_len,
+            length: payload.len() as u32,
+        };
+        let written = self.segment.write_all(&record).and_then(|_| self.segment.sync_data());
+        if let Err(e) = written {
+            self.segment.set_len(self.segment_len)?;
+            return Err(e);
+        }
+
+        let indexed = self.index.write_all(&encode_entry(&hash, &location)).and_then(|_| self.index.sync_data());
+        if let Err(e) = indexed {
+            self.index.set_len((self.locations.len() * INDEX_ENTRY_SIZE) as u64)?;
+            self.segment.set_len(self.segment_len)?;
+            return Err(e);
+        }
+
+        self.segment_len += record.len() as u64;
+        self.insert(hash, location);
+        Ok(())
+    }
+
+    fn read_record(&self, location: &Location) -> io::Result<Vec<u8>> {
+        let mut f = File::open(segment_path(&self.dir, location.segment))?;
+        f.seek(SeekFrom::Start(location.offset))?;
+        let mut record = vec![0; RECORD_HEADER_SIZE as usize + location.length as usize];
+        f.read_exact(&mut record)?;
+        Ok(record)
+    }
+
+    /// Every stored block in height order, so parents come before their children. Blocks that fail their checksum
+    /// are quarantined and dropped from the index.
+    pub fn blocks(&mut self) -> io::Result<Vec<Block>> {
+        let mut blocks = Vec::with_capacity(self.len());
+        let mut corrupt = vec![];
+        for hashes in self.heights.values() {
+            for hash in hashes {
+                let location = self.locations[hash];
+                let record = self.read_record(&location)?;
+                match decode_record(&record) {
+                    Some(block) => {
+                        if block.hash() == *hash {
+                            blocks.push(block);
+                        } else {
+                            corrupt.push((*hash, location, record));
+                        }
+                    }
+                    None => corrupt.push((*hash, location, record)),
+                }
+            }
+        }
+
+        for &(ref hash, ref location, ref record) in corrupt.iter() {
+            println!("Stored block #{} {} is corrupt", location.height, hex::encode(hash));
+            self.quarantine(location.segment, location.offset, record)?;
+            self.remove(hash);
+        }
+        if corrupt.len() > 0 {
+            self.rewrite_index()?;
+        }
+        Ok(blocks)
+    }
+    /// Replaces the saved mempool, written as a single checksummed record
+    pub fn save_mempool(&self, transactions: &[Transaction]) -> io::Result<()> {
+        let mut msg = chain::Mempool::new();
+        for transaction in transactions {
+            let mut data_msg = chain::Block_Data::new();
+            data_msg.set_field_type(chain::Block_DataType::TRANSACTION);
+            data_msg.set_transaction(transaction_to_pb(transaction));
+            msg.mut_data().push(data_msg);
+        }
+        let payload = msg.write_to_bytes().map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        write_atomic(&self.dir.join(MEMPOOL_FILE), &encode_record(&payload))
+    }
+
+    /// Transactions saved by the last run. A corrupt file is quarantined and treated as empty.
+    pub fn load_mempool(&mut self) -> io::Result<Vec<Transaction>> {
+        let path = self.dir.join(MEMPOOL_FILE);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let mut record = vec![];
+        File::open(&path)?.read_to_end(&mut record)?;
+
+        let header_size = RECORD_HEADER_SIZE as usize;
+        let intact = record.len() >= header_size &&
+            from_be_bytes(&record[..4]) == (record.len() - header_size) as u64 &&
+            record[4..8] == checksum(&record[header_size..]);
+        let msg = if intact {
+            protobuf::parse_from_bytes::<chain::Mempool>(&record[header_size..]).ok()
+        } else {
+            None
+        };
+        match msg {
+            Some(ref msg) if msg.get_data().iter().all(p2p::well_formed_data) => {
+                Ok(msg.get_data().iter()
+                    .filter(|data| data.get_field_type() == chain::Block_DataType::TRANSACTION)
+                    .map(|data| pb_to_transaction(data.get_transaction()))
+                    .collect())
+            }
+            _ => {
+                self.quarantine_file(&path)?;
+                Ok(vec![])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+    use super::*;
+    use {BinaryData, BlockData};
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("store-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn block(id: u64) -> Block {
+        let data: Vec<Box<BlockData>> = vec![BinaryData::new(&vec![id as u8])];
+        Block::new(&data, Sha256Hash::default(), -1, id, 1)
+    }
+
+    fn open(dir: &Path) -> BlockStore {
+        BlockStore::open(dir.to_str().unwrap()).unwrap()
+    }
+
+    fn append_bytes(path: &Path, bytes: &[u8]) {
+        OpenOptions::new().append(true).open(path).unwrap().write_all(bytes).unwrap();
+    }
+
+    fn stored_ids(store: &mut BlockStore) -> Vec<u64> {
+        store.blocks().unwrap().iter().map(|block| block.inner.read().unwrap().id).collect()
+    }
+
+    #[test]
+    fn partly_written_block_is_quarantined_and_cut_off() {
+        let dir = test_dir("partly-written");
+        {
+            let mut store = open(&dir);
+            store.append(&block(1)).unwrap();
+            store.append(&block(2)).unwrap();
+        }
+        let segment = segment_path(&dir, 0);
+        let len = fs::metadata(&segment).unwrap().len();
+        let record = encode_record(&block_to_pb(&block(3)).write_to_bytes().unwrap());
+        append_bytes(&segment, &record[..record.len() - 5]);
+
+        let mut store = open(&dir);
+        assert_eq!(store.quarantined(), 1);
+        assert_eq!(stored_ids(&mut store), vec![1, 2]);
+        assert_eq!(fs::metadata(&segment).unwrap().len(), len);
+        assert!(dir.join(QUARANTINE_DIR).join(format!("blk00000-{}.dat", len)).exists());
+        drop(store);
+
+        // Nothing is left to quarantine the next time round
+        assert_eq!(open(&dir).quarantined(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn corrupt_record_after_last_entry_is_only_quarantined_once() {
+        let dir = test_dir("corrupt-tail");
+        {
+            let mut store = open(&dir);
+            store.append(&block(1)).unwrap();
+        }
+        let segment = segment_path(&dir, 0);
+        let len = fs::metadata(&segment).unwrap().len();
+        let mut record = encode_record(&block_to_pb(&block(2)).write_to_bytes().unwrap());
+        let last = record.len() - 1;
+        record[last] ^= 0xff;
+        append_bytes(&segment, &record);
+
+        let mut store = open(&dir);
+        assert_eq!(store.quarantined(), 1);
+        assert_eq!(stored_ids(&mut store), vec![1]);
+        assert_eq!(fs::metadata(&segment).unwrap().len(), len);
+        drop(store);
+
+        let mut store = open(&dir);
+        assert_eq!(store.quarantined(), 0);
+        store.append(&block(3)).unwrap();
+        drop(store);
+        assert_eq!(stored_ids(&mut open(&dir)), vec![1, 3]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn blocks_after_a_corrupt_length_survive_an_index_rebuild() {
+        let dir = test_dir("corrupt-length");
+        let offset = {
+            let mut store = open(&dir);
+            for id in 1..4 {
+                store.append(&block(id)).unwrap();
+            }
+            store.locations[&block(2).hash()].offset
+        };
+        let segment = segment_path(&dir, 0);
+        let len = fs::metadata(&segment).unwrap().len();
+        {
+            let mut f = OpenOptions::new().write(true).open(&segment).unwrap();
+            f.seek(SeekFrom::Start(offset)).unwrap();
+            f.write_all(&[0xff, 0xff, 0xff, 0xff]).unwrap();
+        }
+        fs::write(dir.join(INDEX_FILE), &[0; INDEX_ENTRY_SIZE]).unwrap();
+
+        let mut store = open(&dir);
+        assert_eq!(store.quarantined(), 1);
+        assert_eq!(stored_ids(&mut store), vec![1, 3]);
+        assert_eq!(fs::metadata(&segment).unwrap().len(), len);
+        drop(store);
+
+        assert_eq!(open(&dir).quarantined(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn read_only_open_leaves_the_directory_alone() {
+        let dir = test_dir("read-only");
+        {
+            let mut store = open(&dir);
+            store.append(&block(1)).unwrap();
+            store.append(&block(2)).unwrap();
+        }
+        let segment = segment_path(&dir, 0);
+        let record = encode_record(&block_to_pb(&block(3)).write_to_bytes().unwrap());
+        append_bytes(&segment, &record[..record.len() - 5]);
+        append_bytes(&dir.join(INDEX_FILE), &[9; 20]);
+        let contents = || (fs::read(&segment).unwrap(), fs::read(dir.join(INDEX_FILE)).unwrap());
+        let before = contents();
+
+        let mut store = BlockStore::open_read_only(dir.to_str().unwrap()).unwrap();
+        assert_eq!(store.quarantined(), 1);
+        assert_eq!(stored_ids(&mut store), vec![1, 2]);
+        assert!(contents() == before);
+        assert!(!dir.join(QUARANTINE_DIR).exists());
+        drop(store);
+
+        assert_eq!(open(&dir).quarantined(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn corruption_at_a_quarantined_offset_gets_its_own_file() {
+        let dir = test_dir("quarantine-again");
+        {
+            let mut store = open(&dir);
+            store.append(&block(1)).unwrap();
+        }
+        let segment = segment_path(&dir, 0);
+        let len = fs::metadata(&segment).unwrap().len();
+        let quarantine = dir.join(QUARANTINE_DIR);
+        append_bytes(&segment, &[1, 2, 3]);
+        assert_eq!(open(&dir).quarantined(), 1);
+        append_bytes(&segment, &[4, 5]);
+        assert_eq!(open(&dir).quarantined(), 1);
+
+        assert_eq!(fs::read(quarantine.join(format!("blk00000-{}.dat", len))).unwrap(), vec![1, 2, 3]);
+        assert_eq!(fs::read(quarantine.join(format!("blk00000-{}-1.dat", len))).unwrap(), vec![4, 5]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
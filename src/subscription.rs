@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use futures::{Future, Sink, Stream};
+use futures::sync::mpsc;
+use hex;
+use juniper;
+use juniper::FieldResult;
+use juniper::http::GraphQLRequest;
+use serde_json;
+use warp::ws::{Message, WebSocket};
+use events::Event;
+use history::{self, Direction};
+use scalar::U64;
+use {Block, Context, Sha256Hash, Transaction, TransactionState, sha256hash_from_slice};
+
+/// Websocket sub-protocol spoken by Apollo's subscriptions-transport-ws client
+pub const PROTOCOL: &str = "graphql-ws";
+
+/// What subscription queries run with, the event being delivered alongside the usual context
+pub struct EventContext {
+    context: Context,
+    /// Unset while checking a new subscription's query
+    event: Option<Event>,
+}
+
+impl juniper::Context for EventContext {}
+
+impl juniper::FromContext<EventContext> for Context {
+    fn from(value: &EventContext) -> &Context {
+        &value.context
+    }
+}
+
+/// A transaction sending to or from a subscribed address, either pending or mined in a main chain block
+#[derive(Debug, Clone)]
+pub struct AddressActivity {
+    transaction: Transaction,
+    direction: Direction,
+    counterparty: Sha256Hash,
+    /// Hash and height of the block the transaction was mined in
+    block: Option<(Sha256Hash, u64)>,
+}
+
+graphql_object!(AddressActivity: () |&self|{
+    field transaction() -> Transaction {
+        self.transaction.clone()
+    }
+
+    field direction() -> Direction {
+        self.direction
+    }
+
+    field counterparty() -> String {
+        hex::encode_upper(&self.counterparty)
+    }
+
+    field state() -> TransactionState {
+        match self.block {
+            Some(_) => TransactionState::Confirmed,
+            None => TransactionState::Pending
+        }
+    }
+
+    field block_hash() -> Option<String> {
+        self.block.map(|(hash, _)| hex::encode_upper(&hash))
+    }
+
+    field block_id() -> Option<U64> {
+        self.block.map(|(_, height)| U64(height))
+    }
+});
+
+fn address_activity(transaction: &Transaction, address: &Sha256Hash, block: Option<(Sha256Hash, u64)>)
+                    -> Option<AddressActivity> {
+    if transaction.sender != *address && transaction.recipient != *address {
+        return None;
+    }
+    let (direction, counterparty) = history::direction(transaction, address);
+    Some(AddressActivity {
+        transaction: transaction.clone(),
+        direction,
+        counterparty,
+        block,
+    })
+}
+
+/// Root of subscription operations. Each field is null unless the event being delivered is one it subscribes to,
+/// and events that leave every field null aren't sent.
+pub struct Subscription;
+
+graphql_object!(Subscription: EventContext |&self| {
+    field newBlocks(&executor) -> Option<Block> {
+        match executor.context().event {
+            Some(Event::Block(ref block)) => Some(block.clone()),
+            _ => None
+        }
+    }
+
+    field newPendingTransactions(&executor) -> Option<Transaction> {
+        match executor.context().event {
+            Some(Event::Transaction(ref transaction)) => Some(transaction.clone()),
+            _ => None
+        }
+    }
+
+    field addressActivity(&executor, address: String) -> FieldResult<Option<Vec<AddressActivity>>> {
+        let address = match hex::decode(address) {
+            Ok(ref address) if address.len() == 32 => sha256hash_from_slice(address),
+            _ => {
+                return Err(juniper::FieldError::new("Invalid address", graphql_value!(None)));
+            }
+        };
+        let activity: Vec<AddressActivity> = match executor.context().event {
+            Some(Event::Block(ref block)) => {
+                let inner = block.inner.read().unwrap();
+                let location = Some((inner.hash(), inner.id));
+                inner.data.iter()
+                    .filter_map(|data| data.as_transaction())
+                    .filter_map(|transaction| address_activity(transaction, &address, location))
+                    .collect()
+            }
+            Some(Event::Transaction(ref transaction)) => {
+                address_activity(transaction, &address, None).into_iter().collect()
+            }
+            None => vec![]
+        };
+        if activity.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(activity))
+        }
+    }
+});
+
+type SubscriptionSchema = juniper::RootNode<'static, Subscription, juniper::EmptyMutation<EventContext>>;
+
+#[derive(Deserialize)]
+struct ClientMessage {
+    #[serde(rename = "type")]
+    kind: String,
+    id: Option<String>,
+    payload: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct StartPayload {
+    query: String,
+    #[serde(rename = "operationName")]
+    operation_name: Option<String>,
+    variables: Option<juniper::InputValue>,
+}
+
+#[derive(Serialize)]
+struct ServerMessage<'a> {
+    #[serde(rename = "type")]
+    kind: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<serde_json::Value>,
+}
+
+/// juniper only parses queries and mutations, so a subscription operation is run as a query against the
+/// subscription root
+fn as_query(document: &str) -> String {
+    let document = document.trim_start();
+    if document.starts_with("subscription") {
+        format!("query{}", &document["subscription".len()..])
+    } else {
+        document.to_string()
+    }
+}
+
+struct Connection {
+    context: Context,
+    schema: SubscriptionSchema,
+    /// Queries of the running subscriptions by the id the client gave them
+    subscriptions: HashMap<String, GraphQLRequest>,
+    outgoing: mpsc::UnboundedSender<Message>,
+}
+
+impl Connection {
+    fn send(&self, kind: &str, id: Option<&str>, payload: Option<serde_json::Value>) {
+        let message = ServerMessage { kind, id, payload };
+        let text = serde_json::to_string(&message).expect("Failed to serialise subscription message");
+        // Only fails once the connection is closing
+        let _ = self.outgoing.unbounded_send(Message::text(text));
+    }
+
+    fn execute(&self, request: &GraphQLRequest, event: Option<Event>) -> serde_json::Value {
+        let context = EventContext {
+            context: self.context.clone(),
+            event,
+        };
+        serde_json::to_value(&request.execute(&self.schema, &context)).expect("Failed to serialise GraphQL response")
+    }
+
+    /// Handles a message from the client, returning false once the client asks to close the connection
+    fn receive(&mut self, text: &str) -> bool {
+        let message: ClientMessage = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(e) => {
+                self.send("connection_error", None, Some(json!({ "message": format!("Invalid message: {}", e) })));
+                return true;
+            }
+        };
+        match (message.kind.as_str(), message.id) {
+            ("connection_init", _) => self.send("connection_ack", None, None),
+            ("connection_terminate", _) => return false,
+            ("start", Some(id)) => self.start(id, message.payload),
+            ("stop", Some(id)) => {
+                if self.subscriptions.remove(&id).is_some() {
+                    self.send("complete", Some(id.as_str()), None);
+                }
+            }
+            (kind, id) => {
+                let error = json!({ "message": format!("Unexpected {} message", kind) });
+                self.send("error", id.as_ref().map(|id| id.as_str()), Some(error));
+            }
+        }
+        true
+    }
+
+    fn start(&mut self, id: String, payload: Option<serde_json::Value>) {
+        let payload: StartPayload = match payload.map(serde_json::from_value) {
+            Some(Ok(payload)) => payload,
+            _ => {
+                self.send("error", Some(id.as_str()), Some(json!({ "message": "Start message has no query" })));
+                return;
+            }
+        };
+        if self.subscriptions.contains_key(&id) {
+            self.send("error", Some(id.as_str()), Some(json!({ "message": "Subscription id is already in use" })));
+            return;
+        }
+
+        let request = GraphQLRequest::new(as_query(&payload.query), payload.operation_name, payload.variables);
+        // Without an event every field is null, so this only reports invalid queries
+        match self.execute(&request, None).get("errors") {
+            Some(errors) => {
+                self.send("error", Some(id.as_str()), Some(errors.clone()));
+                return;
+            }
+            None => {}
+        }
+        self.subscriptions.insert(id, request);
+    }
+
+    fn deliver(&self, event: Event) {
+        for (id, request) in self.subscriptions.iter() {
+            let response = self.execute(request, Some(event.clone()));
+            let subscribed = match response.get("data") {
+                Some(&serde_json::Value::Object(ref fields)) => fields.values().any(|value| !value.is_null()),
+                _ => false
+            };
+            if subscribed || response.get("errors").is_some() {
+                self.send("data", Some(id.as_str()), Some(response));
+            }
+        }
+    }
+}
+
+/// Runs subscriptions for a websocket client until it disconnects
+pub fn serve(socket: WebSocket, context: Context) -> impl Future<Item = (), Error = ()> {
+    let events = context.blockchain.read().unwrap().events.subscribe();
+    let (outgoing, outgoing_messages) = mpsc::unbounded();
+    let connection = Arc::new(Mutex::new(Connection {
+        context,
+        schema: SubscriptionSchema::new(Subscription, juniper::EmptyMutation::new()),
+        subscriptions: HashMap::new(),
+        outgoing,
+    }));
+    let (sink, stream) = socket.split();
+
+    let connection_2 = Arc::clone(&connection);
+    let received = stream
+        .map_err(|e| println!("Subscription connection failed: {}", e))
+        .take_while(|message| Ok(!message.is_close()))
+        .for_each(move |message| {
+            match message.to_str() {
+                Ok(text) => {
+                    if !connection_2.lock().unwrap().receive(text) {
+                        return Err(());
+                    }
+                }
+                Err(_) => {}
+            }
+            Ok(())
+        });
+    let delivered = events.for_each(move |event| {
+        connection.lock().unwrap().deliver(event);
+        Ok(())
+    });
+    let sent = outgoing_messages
+        .forward(sink.sink_map_err(|e| println!("Subscription connection failed: {}", e)))
+        .map(|_| ());
+
+    received.select(delivered).map(|_| ()).map_err(|_| ())
+        .select(sent).map(|_| ()).map_err(|_| ())
+}
@@ -0,0 +1,123 @@
+use std::io;
+use std::thread;
+use std::time;
+use std::net::{TcpStream, SocketAddr};
+use std::sync::{Arc, RwLock};
+use hex;
+use proto::chain;
+use p2p;
+use validation::BlockError;
+use {Block, Blockchain, Sha256Hash, pb_to_block, pb_header_to_block};
+
+const READ_TIMEOUT: u64 = 30;
+const RETRY_INTERVAL: u64 = 5;
+/// Failed attempts in a row, without getting any new blocks, before leaving the rest to the peer connection
+const MAX_ATTEMPTS: u32 = 5;
+
+fn sync_error(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Keeps trying to sync from `peer` until we have everything it has. Gives up after `MAX_ATTEMPTS` failures with
+/// no progress, as the peer is also connected to normally and blocks are caught up from there.
+pub fn sync_until_done(blockchain: &Arc<RwLock<Blockchain>>, peer: SocketAddr) {
+    let mut attempts = 0;
+    loop {
+        let height = blockchain.read().unwrap().main_chain.len();
+        match sync_from_peer(blockchain, peer) {
+            Ok(_) => return,
+            Err(e) => {
+                println!("Sync from {} failed: {}", peer, e);
+            }
+        }
+        if blockchain.read().unwrap().main_chain.len() > height {
+            attempts = 0;
+        }
+        attempts += 1;
+        if attempts >= MAX_ATTEMPTS {
+            println!("Giving up on syncing from {} after {} attempts", peer, attempts);
+            return;
+        }
+        thread::sleep(time::Duration::from_secs(RETRY_INTERVAL));
+    }
+}
+
+/// Downloads the peer's main chain, header first then block by block. Each block is added to the chain as soon
+/// as it's validated, so after an interruption the next sync carries on from wherever the stored chain got to.
+pub fn sync_from_peer(blockchain: &Arc<RwLock<Blockchain>>, peer: SocketAddr) -> io::Result<()> {
+    let mut stream = TcpStream::connect(peer)?;
+    stream.set_read_timeout(Some(time::Duration::from_secs(READ_TIMEOUT)))?;
+    let magic = blockchain.read().unwrap().params.magic;
+
+    let handshake = p2p::handshake_envelope(&blockchain.read().unwrap());
+    p2p::write_envelope(&mut stream, &magic, &handshake)?;
+    let msg = p2p::read_envelope(&mut stream, &magic)?;
+    if msg.get_field_type() != chain::Envelope_MessageType::HANDSHAKE || !msg.has_handshake() {
+        return Err(sync_error("expected a handshake".to_string()));
+    }
+    p2p::check_handshake(msg.get_handshake())?;
+    println!("Syncing from {}, which is at block #{}", peer, msg.get_handshake().get_height());
+
+    loop {
+        let locator = blockchain.read().unwrap().locator();
+        let headers = request_headers(&mut stream, &magic, &locator)?;
+        let hashes = p2p::check_headers(&blockchain.read().unwrap(), &headers)?;
+        if hashes.len() == 0 {
+            let height = blockchain.read().unwrap().main_chain.len();
+            println!("Synced {} blocks from {}", height, peer);
+            return Ok(());
+        }
+        for batch in hashes.chunks(p2p::BLOCKS_IN_FLIGHT) {
+            download_blocks(&mut stream, &magic, blockchain, batch)?;
+        }
+    }
+}
+
+fn request_headers(stream: &mut TcpStream, magic: &[u8; 4], locator: &[Sha256Hash]) -> io::Result<Vec<Block>> {
+    p2p::write_envelope(stream, magic, &p2p::get_headers_envelope(locator))?;
+
+    // The peer may gossip other messages to us before it answers
+    loop {
+        let msg = p2p::read_envelope(stream, magic)?;
+        if msg.get_field_type() != chain::Envelope_MessageType::HEADERS || !msg.has_headers() {
+            continue;
+        }
+        let headers = msg.get_headers().get_headers();
+        if headers.len() > p2p::MAX_HEADERS || !headers.iter().all(p2p::well_formed_header) {
+            return Err(sync_error("malformed headers".to_string()));
+        }
+        return Ok(headers.iter().map(pb_header_to_block).collect());
+    }
+}
+
+fn download_blocks(stream: &mut TcpStream, magic: &[u8; 4], blockchain: &Arc<RwLock<Blockchain>>,
+                   hashes: &[Sha256Hash]) -> io::Result<()> {
+    for hash in hashes {
+        p2p::write_envelope(stream, magic, &p2p::get_block_envelope(hash))?;
+    }
+
+    let mut received = 0;
+    while received < hashes.len() {
+        let msg = p2p::read_envelope(stream, magic)?;
+        if msg.get_field_type() != chain::Envelope_MessageType::BLOCK || !msg.has_block() ||
+            !p2p::well_formed_block(msg.get_block()) {
+            continue;
+        }
+        let block = pb_to_block(msg.get_block());
+        // Anything else is a block the peer is announcing rather than one we asked for
+        if block.hash() != hashes[received] {
+            continue;
+        }
+        received += 1;
+
+        let id = block.inner.read().unwrap().id;
+        let result = blockchain.write().unwrap().add_block(block);
+        match result {
+            Ok(_) | Err(BlockError::AlreadyKnown) => {}
+            Err(e) => {
+                return Err(sync_error(format!("block #{} {}: {}", id, hex::encode(hashes[received - 1]), e)));
+            }
+        }
+    }
+    Ok(())
+}
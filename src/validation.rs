@@ -0,0 +1,172 @@
+use std::fmt;
+use chrono::prelude::*;
+use num_bigint::BigUint;
+use num_traits::One;
+use hex;
+use params::NetworkParams;
+use {Block, Blockchain, Sha256Hash, MAX_BLOCK_SIZE};
+
+/// Number of ancestors whose median timestamp a new block must be later than
+pub const MEDIAN_TIME_SPAN: usize = 11;
+/// How far into the future (in seconds) a block's timestamp may be
+pub const MAX_FUTURE_BLOCK_TIME: i64 = 2 * 60 * 60;
+
+#[derive(Debug)]
+pub enum BlockError {
+    AlreadyKnown,
+    GenesisExists,
+    NoGenesis,
+    NotGenesis(u64),
+    UnknownParent(Sha256Hash),
+    WrongHeight { expected: u64, actual: u64 },
+    TimestampTooEarly { median_time_past: i64, timestamp: i64 },
+    TimestampTooLate { max_timestamp: i64, timestamp: i64 },
+    WrongDifficulty { expected: u8, actual: u8 },
+    InsufficientWork,
+    WrongMerkleRoot,
+    TooLarge { size: usize, max: usize },
+    InvalidData(usize),
+    MissingCoinbase,
+    UnexpectedCoinbase(usize),
+    WrongCoinbaseHeight { expected: u64, actual: u64 },
+    WrongCoinbaseAmount { expected: u64, actual: u64 },
+    Overspend(String),
+    Storage(String),
+}
+
+impl fmt::Display for BlockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlockError::AlreadyKnown => write!(f, "block is already known"),
+            BlockError::GenesisExists => write!(f, "chain already has a genesis block"),
+            BlockError::NoGenesis => write!(f, "chain has no genesis block"),
+            BlockError::NotGenesis(id) => write!(f, "block #{} is not a genesis block", id),
+            BlockError::UnknownParent(hash) => write!(f, "parent block {} is unknown", hex::encode_upper(hash)),
+            BlockError::WrongHeight { expected, actual } =>
+                write!(f, "block id is {} but its parent requires {}", actual, expected),
+            BlockError::TimestampTooEarly { median_time_past, timestamp } =>
+                write!(f, "timestamp {} is not after the median time past {}", timestamp, median_time_past),
+            BlockError::TimestampTooLate { max_timestamp, timestamp } =>
+                write!(f, "timestamp {} is after the latest allowed {}", timestamp, max_timestamp),
+            BlockError::WrongDifficulty { expected, actual } =>
+                write!(f, "difficulty is {} but the chain requires {}", actual, expected),
+            BlockError::InsufficientWork => write!(f, "hash does not meet the difficulty target"),
+            BlockError::WrongMerkleRoot => write!(f, "merkle root does not match the block data"),
+            BlockError::TooLarge { size, max } => write!(f, "block data is {} bytes, more than the {} allowed", size, max),
+            BlockError::InvalidData(index) => write!(f, "data entry {} is invalid", index),
+            BlockError::MissingCoinbase => write!(f, "first data entry is not a coinbase"),
+            BlockError::UnexpectedCoinbase(index) => write!(f, "data entry {} is an extra coinbase", index),
+            BlockError::WrongCoinbaseHeight { expected, actual } =>
+                write!(f, "coinbase height is {} but should be {}", actual, expected),
+            BlockError::WrongCoinbaseAmount { expected, actual } =>
+                write!(f, "coinbase pays {} but should pay {}", actual, expected),
+            BlockError::Overspend(e) => write!(f, "{}", e),
+            BlockError::Storage(e) => write!(f, "failed to store block: {}", e),
+        }
+    }
+}
+
+/// Median timestamp of the block at `index` and its ancestors, up to `MEDIAN_TIME_SPAN` blocks
+pub fn median_time_past(chain: &Blockchain, index: usize) -> i64 {
+    let mut timestamps = vec![];
+    let mut index = index as i64;
+    while index >= 0 && timestamps.len() < MEDIAN_TIME_SPAN {
+        let inner = chain.blocks[index as usize].inner.read().unwrap();
+        timestamps.push(inner.timestamp);
+        index = inner.prev_block_index;
+    }
+    timestamps.sort();
+    timestamps[timestamps.len() / 2]
+}
+
+/// Whether a block hash is below the target for the given difficulty
+pub fn meets_target(hash: &Sha256Hash, difficulty: u8) -> bool {
+    let target = BigUint::one() << (256 - difficulty as usize);
+    BigUint::from_bytes_be(hash) < target
+}
+
+/// Checks that only depend on the block itself: proof of work and the validity of its data
+pub fn check_block_contents(params: &NetworkParams, block: &Block, difficulty: u8) -> Result<(), BlockError> {
+    let inner = block.inner.read().unwrap();
+
+    if inner.difficulty != difficulty {
+        return Err(BlockError::WrongDifficulty { expected: difficulty, actual: inner.difficulty });
+    }
+    if !meets_target(&inner.hash(), difficulty) {
+        return Err(BlockError::InsufficientWork);
+    }
+
+    if inner.merkle_root != inner.data_merkle_root() {
+        return Err(BlockError::WrongMerkleRoot);
+    }
+
+    let size = inner.data.iter().map(|data| data.data().len()).sum();
+    if size > MAX_BLOCK_SIZE {
+        return Err(BlockError::TooLarge { size, max: MAX_BLOCK_SIZE });
+    }
+
+    for (i, data) in inner.data.iter().enumerate() {
+        if !data.is_valid() {
+            return Err(BlockError::InvalidData(i));
+        }
+    }
+
+    // Every block after genesis must start with exactly one coinbase paying the block reward plus fees
+    let first_coinbase = if inner.id == 0 { 0 } else { 1 };
+    for (i, data) in inner.data.iter().enumerate().skip(first_coinbase) {
+        if data.as_coinbase().is_some() {
+            return Err(BlockError::UnexpectedCoinbase(i));
+        }
+    }
+    if inner.id == 0 {
+        return Ok(());
+    }
+    let coinbase = match inner.data.first().and_then(|data| data.as_coinbase()) {
+        Some(coinbase) => coinbase,
+        None => return Err(BlockError::MissingCoinbase)
+    };
+    if coinbase.height != inner.id {
+        return Err(BlockError::WrongCoinbaseHeight { expected: inner.id, actual: coinbase.height });
+    }
+    let fees = inner.data.iter().fold(0 as u64, |total, data| total.saturating_add(data.fee()));
+    let expected_amount = params.block_reward(inner.id).saturating_add(fees);
+    if coinbase.amount != expected_amount {
+        return Err(BlockError::WrongCoinbaseAmount { expected: expected_amount, actual: coinbase.amount });
+    }
+
+    Ok(())
+}
+
+/// Checks a block against its position in the block tree, returning the index of its parent
+pub fn check_block(chain: &Blockchain, block: &Block) -> Result<usize, BlockError> {
+    let hash = block.hash();
+    if chain.hash_index_map.contains_key(&hash) {
+        return Err(BlockError::AlreadyKnown);
+    }
+
+    let (id, timestamp, prev_block_hash) = {
+        let inner = block.inner.read().unwrap();
+        (inner.id, inner.timestamp, inner.prev_block_hash)
+    };
+    let parent_index = match chain.hash_index_map.get(&prev_block_hash) {
+        Some(index) => *index as usize,
+        None => return Err(BlockError::UnknownParent(prev_block_hash))
+    };
+
+    let expected_id = chain.blocks[parent_index].inner.read().unwrap().id + 1;
+    if id != expected_id {
+        return Err(BlockError::WrongHeight { expected: expected_id, actual: id });
+    }
+
+    let median_time_past = median_time_past(chain, parent_index);
+    if timestamp <= median_time_past {
+        return Err(BlockError::TimestampTooEarly { median_time_past, timestamp });
+    }
+    let max_timestamp = Utc::now().timestamp() + MAX_FUTURE_BLOCK_TIME;
+    if timestamp > max_timestamp {
+        return Err(BlockError::TimestampTooLate { max_timestamp, timestamp });
+    }
+
+    check_block_contents(chain.params, block, chain.next_difficulty(parent_index))?;
+    Ok(parent_index)
+}